@@ -5,7 +5,7 @@ use actix_web::{
 };
 use common::{
     context::GeneralContext,
-    entities::{auditor::PublicAuditor, badge::PublicBadge},
+    entities::{auditor::PublicAuditor, badge::PublicBadge, organization::PublicOrganization},
     error,
 };
 use mongodb::bson::{oid::ObjectId, Document};
@@ -52,6 +52,28 @@ pub async fn get_badges_data(
     Ok(Json(IndexerService::new(context).find_badges(ids).await?))
 }
 
+#[get("/organization/data/{since}")]
+pub async fn provide_organization_data(
+    context: GeneralContext,
+    since: web::Path<i64>,
+) -> error::Result<Json<Vec<Document>>> {
+    Ok(Json(
+        IndexerService::new(context)
+            .index_organizations(since.into_inner())
+            .await?,
+    ))
+}
+
+#[post("/organization/data")]
+pub async fn get_organization_data(
+    context: GeneralContext,
+    Json(ids): web::Json<Vec<ObjectId>>,
+) -> error::Result<Json<Vec<PublicOrganization>>> {
+    Ok(Json(
+        IndexerService::new(context).find_organizations(ids).await?,
+    ))
+}
+
 #[get("/auditors/ping")]
 pub async fn ping() -> HttpResponse {
     HttpResponse::Ok().finish()
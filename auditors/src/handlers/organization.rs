@@ -0,0 +1,88 @@
+use actix_web::{
+    delete, get, patch, post,
+    web::{self, Json},
+};
+
+use common::{context::GeneralContext, entities::organization::PublicOrganization, error};
+
+use crate::service::organization::{
+    AddMember, CreateOrganization, OrganizationChange, OrganizationService,
+};
+
+#[post("/organization")]
+pub async fn post_organization(
+    context: GeneralContext,
+    Json(data): web::Json<CreateOrganization>,
+) -> error::Result<Json<PublicOrganization>> {
+    Ok(Json(OrganizationService::new(context).create(data).await?))
+}
+
+#[get("/organization/{id}")]
+pub async fn get_organization(
+    context: GeneralContext,
+    id: web::Path<String>,
+) -> error::Result<Json<PublicOrganization>> {
+    Ok(Json(
+        OrganizationService::new(context).find(id.parse()?).await?,
+    ))
+}
+
+#[get("/my_organization")]
+pub async fn get_my_organization(
+    context: GeneralContext,
+) -> error::Result<Json<Vec<PublicOrganization>>> {
+    Ok(Json(
+        OrganizationService::new(context).my_organizations().await?,
+    ))
+}
+
+#[patch("/organization/{id}")]
+pub async fn patch_organization(
+    context: GeneralContext,
+    id: web::Path<String>,
+    Json(data): Json<OrganizationChange>,
+) -> error::Result<Json<PublicOrganization>> {
+    Ok(Json(
+        OrganizationService::new(context)
+            .change(id.parse()?, data)
+            .await?,
+    ))
+}
+
+#[delete("/organization/{id}")]
+pub async fn delete_organization(
+    context: GeneralContext,
+    id: web::Path<String>,
+) -> error::Result<Json<PublicOrganization>> {
+    Ok(Json(
+        OrganizationService::new(context)
+            .delete(id.parse()?)
+            .await?,
+    ))
+}
+
+#[post("/organization/{id}/member")]
+pub async fn post_organization_member(
+    context: GeneralContext,
+    id: web::Path<String>,
+    Json(data): Json<AddMember>,
+) -> error::Result<Json<PublicOrganization>> {
+    Ok(Json(
+        OrganizationService::new(context)
+            .add_member(id.parse()?, data)
+            .await?,
+    ))
+}
+
+#[delete("/organization/{id}/member/{user_id}")]
+pub async fn delete_organization_member(
+    context: GeneralContext,
+    path: web::Path<(String, String)>,
+) -> error::Result<Json<PublicOrganization>> {
+    let (id, user_id) = path.into_inner();
+    Ok(Json(
+        OrganizationService::new(context)
+            .remove_member(id.parse()?, user_id.parse()?)
+            .await?,
+    ))
+}
@@ -15,7 +15,14 @@ use common::{context::effectfull_context::ServiceState, services::API_PREFIX};
 pub use handlers::auditor::*;
 use handlers::{
     badge::{delete, find_badge, merge, post_badge},
-    indexer::{get_auditor_data, get_badges_data, ping, provide_auditor_data, provide_badges_data},
+    indexer::{
+        get_auditor_data, get_badges_data, get_organization_data, ping, provide_auditor_data,
+        provide_badges_data, provide_organization_data,
+    },
+    organization::{
+        delete_organization, delete_organization_member, get_my_organization, get_organization,
+        patch_organization, post_organization, post_organization_member,
+    },
 };
 
 pub fn create_app(
@@ -50,7 +57,16 @@ pub fn create_app(
                 .service(post_badge)
                 .service(merge)
                 .service(delete)
-                .service(find_badge),
+                .service(find_badge)
+                .service(post_organization)
+                .service(get_my_organization)
+                .service(get_organization)
+                .service(patch_organization)
+                .service(delete_organization)
+                .service(post_organization_member)
+                .service(delete_organization_member)
+                .service(provide_organization_data)
+                .service(get_organization_data),
         );
     app
 }
@@ -8,6 +8,7 @@ use common::auth::Service;
 use common::context::effectfull_context::ServiceState;
 use common::entities::auditor::Auditor;
 use common::entities::badge::Badge;
+use common::entities::organization::Organization;
 use common::repository::mongo_repository::MongoRepository;
 use mongodb::bson::oid::ObjectId;
 
@@ -21,10 +22,13 @@ async fn main() -> std::io::Result<()> {
         MongoRepository::new(&mongo_uri, "auditors", "auditors").await;
     let badge_repo: MongoRepository<Badge<ObjectId>> =
         MongoRepository::new(&mongo_uri, "badges", "badges").await;
+    let organization_repo: MongoRepository<Organization<ObjectId>> =
+        MongoRepository::new(&mongo_uri, "auditors", "organizations").await;
 
     let mut state = ServiceState::new(Service::Auditors);
     state.insert(Arc::new(auditor_repo));
     state.insert(Arc::new(badge_repo));
+    state.insert(Arc::new(organization_repo));
     let state = Arc::new(state);
 
     HttpServer::new(move || create_app(state.clone()))
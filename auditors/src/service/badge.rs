@@ -293,7 +293,7 @@ impl BadgeService {
         // create new audit requests
 
         for request in &mut requests {
-            if &request.customer_id == &id_str {
+            if request.customer_id == id_str {
                 continue;
             }
             // CreateRequest
@@ -304,6 +304,8 @@ impl BadgeService {
                 price: request.price,
                 description: request.description.clone(),
                 time: request.time.clone(),
+                expires_at: request.expires_at,
+                organization_id: request.organization_id.clone(),
             };
 
             let auth = Auth::User(request.customer_id.parse()?);
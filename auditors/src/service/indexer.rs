@@ -4,6 +4,7 @@ use common::{
     entities::{
         auditor::{Auditor, PublicAuditor},
         badge::{Badge, PublicBadge},
+        organization::{Organization, PublicOrganization},
     },
     error::{self, AddCode},
 };
@@ -85,4 +86,47 @@ impl IndexerService {
             .map(|x| auth.public_badge(x))
             .collect::<Vec<_>>())
     }
+
+    pub async fn index_organizations(&self, since: i64) -> error::Result<Vec<Document>> {
+        let auth = self.context.auth();
+
+        if !GetData.get_access(&auth, ()) {
+            return Err(anyhow::anyhow!("No access to get organization data {:?}", auth).code(400));
+        }
+
+        let organizations = self
+            .context
+            .try_get_repository::<Organization<ObjectId>>()?;
+
+        let organizations = organizations.get_all_since(since).await?;
+
+        Ok(organizations
+            .into_iter()
+            .filter_map(|x| x.into())
+            .collect::<Vec<_>>())
+    }
+
+    pub async fn find_organizations(
+        &self,
+        ids: Vec<ObjectId>,
+    ) -> error::Result<Vec<PublicOrganization>> {
+        let auth = self.context.auth();
+
+        if !GetData.get_access(&auth, ()) {
+            return Err(
+                anyhow::anyhow!("No access to get organization data: {:?}", auth).code(400),
+            );
+        }
+
+        let organizations = self
+            .context
+            .try_get_repository::<Organization<ObjectId>>()?;
+
+        let organizations = organizations.find_all_by_ids("id", ids).await?;
+
+        Ok(organizations
+            .into_iter()
+            .map(|x| auth.public_organization(x))
+            .collect::<Vec<_>>())
+    }
 }
@@ -1,3 +1,4 @@
 pub mod auditor;
 pub mod badge;
 pub mod indexer;
+pub mod organization;
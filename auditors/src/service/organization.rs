@@ -0,0 +1,313 @@
+use chrono::Utc;
+use common::{
+    access_rules::{AccessRules, Edit},
+    api::seartch::delete_from_search,
+    context::GeneralContext,
+    entities::{
+        audit_request::PriceRange,
+        auditor::Auditor,
+        contacts::Contacts,
+        organization::{Organization, OrganizationMember, OrganizationRole, PublicOrganization},
+    },
+    error::{self, AddCode},
+};
+use mongodb::bson::{oid::ObjectId, Bson};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateOrganization {
+    pub name: String,
+    pub avatar: Option<String>,
+    pub about: Option<String>,
+    pub contacts: Contacts,
+    pub tags: Option<Vec<String>>,
+    pub price_range: Option<PriceRange>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrganizationChange {
+    name: Option<String>,
+    avatar: Option<String>,
+    about: Option<String>,
+    contacts: Option<Contacts>,
+    tags: Option<Vec<String>>,
+    price_range: Option<PriceRange>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AddMember {
+    pub user_id: String,
+    pub role: OrganizationRole,
+}
+
+pub struct OrganizationService {
+    context: GeneralContext,
+}
+
+impl OrganizationService {
+    pub fn new(context: GeneralContext) -> Self {
+        Self { context }
+    }
+
+    async fn get(&self, id: ObjectId) -> error::Result<Organization<ObjectId>> {
+        let organizations = self
+            .context
+            .try_get_repository::<Organization<ObjectId>>()?;
+
+        let Some(organization) = organizations.find("id", &Bson::ObjectId(id)).await? else {
+            return Err(anyhow::anyhow!("No organization found").code(404));
+        };
+
+        Ok(organization)
+    }
+
+    async fn save(&self, organization: &mut Organization<ObjectId>) -> error::Result<()> {
+        let organizations = self
+            .context
+            .try_get_repository::<Organization<ObjectId>>()?;
+
+        let version = organization.last_modified;
+        organization.last_modified = Utc::now().timestamp_micros().max(version + 1);
+
+        if !organizations
+            .replace("id", &organization.id, version, organization)
+            .await?
+        {
+            return Err(anyhow::anyhow!(
+                "Organization was changed by another user, reload it and try again"
+            )
+            .code(409));
+        }
+
+        Ok(())
+    }
+
+    pub async fn create(
+        &self,
+        organization: CreateOrganization,
+    ) -> error::Result<PublicOrganization> {
+        let auth = self.context.auth();
+
+        let organizations = self
+            .context
+            .try_get_repository::<Organization<ObjectId>>()?;
+        let auditors = self.context.try_get_repository::<Auditor<ObjectId>>()?;
+
+        let Some(user_id) = auth.id() else {
+            return Err(anyhow::anyhow!("Organization can be created only by auditor").code(403));
+        };
+
+        if auditors
+            .find("user_id", &Bson::ObjectId(user_id))
+            .await?
+            .is_none()
+        {
+            return Err(anyhow::anyhow!("Organization can be created only by auditor").code(403));
+        }
+
+        if organization.name.trim().is_empty() {
+            return Err(anyhow::anyhow!("Organization name is empty").code(400));
+        }
+
+        let organization = Organization {
+            id: ObjectId::new(),
+            name: organization.name,
+            avatar: organization.avatar.unwrap_or_default(),
+            about: organization.about.unwrap_or_default(),
+            contacts: organization.contacts,
+            tags: organization.tags.unwrap_or_default(),
+            price_range: organization.price_range.unwrap_or_default(),
+            members: vec![OrganizationMember {
+                user_id,
+                role: OrganizationRole::Admin,
+            }],
+            last_modified: Utc::now().timestamp_micros(),
+            created_at: Utc::now().timestamp_micros(),
+        };
+
+        organizations.insert(&organization).await?;
+
+        Ok(auth.public_organization(organization))
+    }
+
+    pub async fn find(&self, id: ObjectId) -> error::Result<PublicOrganization> {
+        let auth = self.context.auth();
+
+        Ok(auth.public_organization(self.get(id).await?))
+    }
+
+    pub async fn my_organizations(&self) -> error::Result<Vec<PublicOrganization>> {
+        let auth = self.context.auth();
+
+        let organizations = self
+            .context
+            .try_get_repository::<Organization<ObjectId>>()?;
+
+        let Some(user_id) = auth.id() else {
+            return Err(anyhow::anyhow!("No user found").code(403));
+        };
+
+        let organizations = organizations
+            .find_many("members.user_id", &Bson::ObjectId(user_id))
+            .await?;
+
+        Ok(organizations
+            .into_iter()
+            .map(|organization| auth.public_organization(organization))
+            .collect())
+    }
+
+    pub async fn change(
+        &self,
+        id: ObjectId,
+        change: OrganizationChange,
+    ) -> error::Result<PublicOrganization> {
+        let auth = self.context.auth();
+
+        let mut organization = self.get(id).await?;
+
+        if !Edit.get_access(&auth, &organization) {
+            return Err(
+                anyhow::anyhow!("User is not available to change this organization").code(403),
+            );
+        }
+
+        if let Some(name) = change.name {
+            if name.trim().is_empty() {
+                return Err(anyhow::anyhow!("Organization name is empty").code(400));
+            }
+            organization.name = name;
+        }
+
+        if let Some(avatar) = change.avatar {
+            organization.avatar = avatar;
+        }
+
+        if let Some(about) = change.about {
+            organization.about = about;
+        }
+
+        if let Some(contacts) = change.contacts {
+            organization.contacts = contacts;
+        }
+
+        if let Some(tags) = change.tags {
+            organization.tags = tags;
+        }
+
+        if let Some(price_range) = change.price_range {
+            organization.price_range = price_range;
+        }
+
+        self.save(&mut organization).await?;
+
+        Ok(auth.public_organization(organization))
+    }
+
+    /// Adds an auditor to the organization, or changes the role of a member.
+    pub async fn add_member(
+        &self,
+        id: ObjectId,
+        member: AddMember,
+    ) -> error::Result<PublicOrganization> {
+        let auth = self.context.auth();
+
+        let auditors = self.context.try_get_repository::<Auditor<ObjectId>>()?;
+
+        let mut organization = self.get(id).await?;
+
+        if !Edit.get_access(&auth, &organization) {
+            return Err(
+                anyhow::anyhow!("User is not available to change this organization").code(403),
+            );
+        }
+
+        let user_id: ObjectId = member.user_id.parse()?;
+
+        if auditors
+            .find("user_id", &Bson::ObjectId(user_id))
+            .await?
+            .is_none()
+        {
+            return Err(anyhow::anyhow!("No auditor found").code(404));
+        }
+
+        organization
+            .members
+            .retain(|member| member.user_id != user_id);
+        organization.members.push(OrganizationMember {
+            user_id,
+            role: member.role,
+        });
+
+        if !organization
+            .members
+            .iter()
+            .any(|member| member.role == OrganizationRole::Admin)
+        {
+            return Err(anyhow::anyhow!("Organization must have an admin").code(400));
+        }
+
+        self.save(&mut organization).await?;
+
+        Ok(auth.public_organization(organization))
+    }
+
+    /// Admins remove members, members can leave by themselves.
+    pub async fn remove_member(
+        &self,
+        id: ObjectId,
+        user_id: ObjectId,
+    ) -> error::Result<PublicOrganization> {
+        let auth = self.context.auth();
+
+        let mut organization = self.get(id).await?;
+
+        if !Edit.get_access(&auth, &organization) && auth.id() != Some(user_id) {
+            return Err(
+                anyhow::anyhow!("User is not available to change this organization").code(403),
+            );
+        }
+
+        if !organization.is_member(&user_id) {
+            return Err(anyhow::anyhow!("User is not a member of this organization").code(404));
+        }
+
+        organization
+            .members
+            .retain(|member| member.user_id != user_id);
+
+        if !organization
+            .members
+            .iter()
+            .any(|member| member.role == OrganizationRole::Admin)
+        {
+            return Err(anyhow::anyhow!("Organization must have an admin").code(400));
+        }
+
+        self.save(&mut organization).await?;
+
+        Ok(auth.public_organization(organization))
+    }
+
+    pub async fn delete(&self, id: ObjectId) -> error::Result<PublicOrganization> {
+        let auth = self.context.auth();
+
+        let organizations = self
+            .context
+            .try_get_repository::<Organization<ObjectId>>()?;
+
+        let organization = self.get(id).await?;
+
+        if !Edit.get_access(&auth, &organization) {
+            return Err(
+                anyhow::anyhow!("User is not available to delete this organization").code(403),
+            );
+        }
+
+        organizations.delete("id", &id).await?;
+        delete_from_search(&self.context, id).await?;
+
+        Ok(auth.public_organization(organization))
+    }
+}
@@ -6,21 +6,38 @@ use actix_web::{
 
 use common::{
     api::{
-        audits::{AuditChange, CreateIssue, NoCustomerAuditRequest, PublicAudit},
+        analytics::{AuditAnalytics, UserAnalytics},
+        audits::{
+            AuditChange, AuditFilter, AuditReason, ChangeReviewRound, CreateIssue,
+            CreateIssueFromTemplate, CreateReviewRound, ExportParams, ImportParams, ImportSummary,
+            NoCustomerAuditRequest, PublicAudit, RemediationSummary, ReportVerification,
+            ResolveDispute, Timeline,
+        },
+        issue::{ChangeComment, CreateComment, PublicComment},
         seartch::PaginationParams,
     },
     context::GeneralContext,
-    entities::{issue::ChangeIssue, role::Role},
-    error,
+    entities::{
+        issue::{parse_issue_code, ChangeIssue},
+        review_round::ReviewRound,
+        role::Role,
+    },
+    error::{self, AddCode},
 };
 
 use serde_json::json;
 
 use crate::service::{
-    audit::{AuditService},
+    activity::ActivityService,
+    analytics::AnalyticsService,
+    audit::{AuditService, MyAuditResult},
     audit_request::PublicRequest,
 };
 
+fn parse_issue_id(issue_id: &str) -> error::Result<usize> {
+    parse_issue_code(issue_id).ok_or_else(|| anyhow::anyhow!("Invalid issue id").code(400))
+}
+
 #[post("/audit")]
 pub async fn post_audit(
     context: GeneralContext,
@@ -57,10 +74,15 @@ pub async fn get_my_audit(
     context: GeneralContext,
     role: web::Path<Role>,
     pagination: Query<PaginationParams>,
-) -> error::Result<Json<Vec<PublicAudit>>> {
+    filter: Query<AuditFilter>,
+) -> error::Result<Json<MyAuditResult>> {
     Ok(Json(
         AuditService::new(context)
-            .my_audit(role.into_inner(), pagination.into_inner())
+            .my_audit(
+                role.into_inner(),
+                pagination.into_inner(),
+                filter.into_inner(),
+            )
             .await?,
     ))
 }
@@ -84,6 +106,45 @@ pub async fn delete_audit(
     Ok(Json(AuditService::new(context).delete(id.parse()?).await?))
 }
 
+#[post("/audit/{id}/cancel")]
+pub async fn post_audit_cancel(
+    context: GeneralContext,
+    id: web::Path<String>,
+    Json(data): Json<AuditReason>,
+) -> error::Result<Json<PublicAudit>> {
+    Ok(Json(
+        AuditService::new(context)
+            .cancel(id.parse()?, data.reason)
+            .await?,
+    ))
+}
+
+#[post("/audit/{id}/dispute")]
+pub async fn post_audit_dispute(
+    context: GeneralContext,
+    id: web::Path<String>,
+    Json(data): Json<AuditReason>,
+) -> error::Result<Json<PublicAudit>> {
+    Ok(Json(
+        AuditService::new(context)
+            .dispute(id.parse()?, data.reason)
+            .await?,
+    ))
+}
+
+#[post("/audit/{id}/dispute/resolve")]
+pub async fn post_audit_dispute_resolve(
+    context: GeneralContext,
+    id: web::Path<String>,
+    Json(data): Json<ResolveDispute>,
+) -> error::Result<Json<PublicAudit>> {
+    Ok(Json(
+        AuditService::new(context)
+            .resolve_dispute(id.parse()?, data)
+            .await?,
+    ))
+}
+
 #[post("/audit/{id}/issue")]
 pub async fn post_audit_issue(
     context: GeneralContext,
@@ -96,14 +157,40 @@ pub async fn post_audit_issue(
     Ok(HttpResponse::Ok().json(result))
 }
 
+#[post("/audit/{id}/issue/template")]
+pub async fn post_audit_issue_from_template(
+    context: GeneralContext,
+    id: web::Path<String>,
+    Json(data): Json<CreateIssueFromTemplate>,
+) -> error::Result<HttpResponse> {
+    let result = AuditService::new(context)
+        .create_issue_from_template(id.parse()?, data)
+        .await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+#[post("/audit/{id}/issue/import")]
+pub async fn post_audit_issue_import(
+    context: GeneralContext,
+    id: web::Path<String>,
+    params: Query<ImportParams>,
+    Json(data): Json<serde_json::Value>,
+) -> error::Result<Json<ImportSummary>> {
+    Ok(Json(
+        AuditService::new(context)
+            .import_issues(id.parse()?, params.into_inner(), data)
+            .await?,
+    ))
+}
+
 #[patch("/audit/{id}/issue/{issue_id}")]
 pub async fn patch_audit_issue(
     context: GeneralContext,
-    id: web::Path<(String, usize)>,
+    id: web::Path<(String, String)>,
     Json(data): Json<ChangeIssue>,
 ) -> error::Result<HttpResponse> {
     let result = AuditService::new(context)
-        .change_issue(id.0.parse()?, id.1, data)
+        .change_issue(id.0.parse()?, parse_issue_id(&id.1)?, data)
         .await?;
     Ok(HttpResponse::Ok().json(result))
 }
@@ -117,13 +204,36 @@ pub async fn get_audit_issue(
     Ok(HttpResponse::Ok().json(result))
 }
 
+#[get("/audit/{id}/issues/export")]
+pub async fn get_audit_issues_export(
+    context: GeneralContext,
+    id: web::Path<String>,
+    params: Query<ExportParams>,
+) -> error::Result<HttpResponse> {
+    let format = params.format;
+    let result = AuditService::new(context)
+        .export_issues(id.parse()?, format)
+        .await?;
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header((
+            "Content-Disposition",
+            format!(
+                "attachment; filename=\"audit-{}-issues.{}\"",
+                id,
+                format.extension()
+            ),
+        ))
+        .body(result))
+}
+
 #[get("/audit/{id}/issue/{issue_id}")]
 pub async fn get_audit_issue_by_id(
     context: GeneralContext,
-    id: web::Path<(String, usize)>,
+    id: web::Path<(String, String)>,
 ) -> error::Result<HttpResponse> {
     let result = AuditService::new(context)
-        .get_issue_by_id(id.0.parse()?, id.1)
+        .get_issue_by_id(id.0.parse()?, parse_issue_id(&id.1)?)
         .await?;
     Ok(HttpResponse::Ok().json(result))
 }
@@ -131,14 +241,59 @@ pub async fn get_audit_issue_by_id(
 #[delete("/audit/{id}/issue/{issue_id}")]
 pub async fn delete_audit_issue(
     context: GeneralContext,
-    id: web::Path<(String, usize)>,
+    id: web::Path<(String, String)>,
 ) -> error::Result<HttpResponse> {
     let result = AuditService::new(context)
-        .delete_issue(id.0.parse()?, id.1)
+        .delete_issue(id.0.parse()?, parse_issue_id(&id.1)?)
         .await?;
     Ok(HttpResponse::Ok().json(result))
 }
 
+#[get("/audit/{id}/remediation")]
+pub async fn get_audit_remediation(
+    context: GeneralContext,
+    id: web::Path<String>,
+) -> error::Result<Json<RemediationSummary>> {
+    Ok(Json(
+        AuditService::new(context).remediation(id.parse()?).await?,
+    ))
+}
+
+#[get("/audit/{id}/timeline")]
+pub async fn get_audit_timeline(
+    context: GeneralContext,
+    id: web::Path<String>,
+    pagination: Query<PaginationParams>,
+) -> error::Result<Json<Timeline>> {
+    Ok(Json(
+        ActivityService::new(context)
+            .timeline(id.parse()?, pagination.into_inner())
+            .await?,
+    ))
+}
+
+#[get("/audit/{id}/analytics")]
+pub async fn get_audit_analytics(
+    context: GeneralContext,
+    id: web::Path<String>,
+) -> error::Result<Json<AuditAnalytics>> {
+    Ok(Json(
+        AnalyticsService::new(context).audit(id.parse()?).await?,
+    ))
+}
+
+#[get("/analytics/{role}/{user_id}")]
+pub async fn get_user_analytics(
+    context: GeneralContext,
+    path: web::Path<(Role, String)>,
+) -> error::Result<Json<UserAnalytics>> {
+    Ok(Json(
+        AnalyticsService::new(context)
+            .user(path.0, path.1.parse()?)
+            .await?,
+    ))
+}
+
 #[patch("/audit/{id}/disclose_all")]
 pub async fn patch_audit_disclose_all(
     context: GeneralContext,
@@ -148,26 +303,118 @@ pub async fn patch_audit_disclose_all(
     Ok(HttpResponse::Ok().json(result))
 }
 
-#[patch("/audit/{id}/{issue_id}/read/{read}")]
+#[post("/audit/{id}/issue/{issue_id}/comment")]
+pub async fn post_audit_issue_comment(
+    context: GeneralContext,
+    id: web::Path<(String, String)>,
+    Json(data): Json<CreateComment>,
+) -> error::Result<Json<PublicComment>> {
+    Ok(Json(
+        AuditService::new(context)
+            .create_comment(id.0.parse()?, parse_issue_id(&id.1)?, data)
+            .await?,
+    ))
+}
+
+#[patch("/audit/{id}/issue/{issue_id}/comment/{comment_id}")]
+pub async fn patch_audit_issue_comment(
+    context: GeneralContext,
+    id: web::Path<(String, String, String)>,
+    Json(data): Json<ChangeComment>,
+) -> error::Result<Json<PublicComment>> {
+    Ok(Json(
+        AuditService::new(context)
+            .change_comment(id.0.parse()?, parse_issue_id(&id.1)?, id.2.parse()?, data)
+            .await?,
+    ))
+}
+
+#[delete("/audit/{id}/issue/{issue_id}/comment/{comment_id}")]
+pub async fn delete_audit_issue_comment(
+    context: GeneralContext,
+    id: web::Path<(String, String, String)>,
+) -> error::Result<Json<PublicComment>> {
+    Ok(Json(
+        AuditService::new(context)
+            .delete_comment(id.0.parse()?, parse_issue_id(&id.1)?, id.2.parse()?)
+            .await?,
+    ))
+}
+
+#[patch("/audit/{id}/issue/{issue_id}/comment/{comment_id}/read")]
+pub async fn patch_audit_issue_comment_read(
+    context: GeneralContext,
+    id: web::Path<(String, String, String)>,
+) -> error::Result<HttpResponse> {
+    AuditService::new(context)
+        .read_comments(id.0.parse()?, parse_issue_id(&id.1)?, Some(id.2.parse()?))
+        .await?;
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[patch("/audit/{id}/issue/{issue_id}/read")]
 pub async fn patch_audit_issue_read(
     context: GeneralContext,
-    id: web::Path<(String, usize, u64)>,
+    id: web::Path<(String, String)>,
 ) -> error::Result<HttpResponse> {
     AuditService::new(context)
-        .read_events(id.0.parse()?, id.1, id.2)
+        .read_comments(id.0.parse()?, parse_issue_id(&id.1)?, None)
         .await?;
     Ok(HttpResponse::Ok().finish())
 }
 
+#[post("/audit/{id}/round")]
+pub async fn post_audit_review_round(
+    context: GeneralContext,
+    id: web::Path<String>,
+    Json(data): Json<CreateReviewRound>,
+) -> error::Result<Json<ReviewRound>> {
+    Ok(Json(
+        AuditService::new(context)
+            .create_review_round(id.parse()?, data)
+            .await?,
+    ))
+}
+
+#[patch("/audit/{id}/round/{round_id}")]
+pub async fn patch_audit_review_round(
+    context: GeneralContext,
+    path: web::Path<(String, usize)>,
+    Json(data): Json<ChangeReviewRound>,
+) -> error::Result<Json<ReviewRound>> {
+    let (id, round_id) = path.into_inner();
+    Ok(Json(
+        AuditService::new(context)
+            .change_review_round(id.parse()?, round_id, data)
+            .await?,
+    ))
+}
+
 #[get("/public_audits/{id}/{role}")]
 pub async fn get_public_audits(
     context: GeneralContext,
     path: web::Path<(String, String)>,
+    pagination: Query<PaginationParams>,
 ) -> error::Result<Json<Vec<PublicAudit>>> {
     let (id, role) = path.into_inner();
     Ok(Json(
         AuditService::new(context)
-            .find_public(id.parse()?, role)
+            .find_public(id.parse()?, role, pagination.into_inner())
+            .await?,
+    ))
+}
+
+/// Body is the report PDF as raw bytes, it is hashed here and compared
+/// with the hash taken when the audit was resolved.
+#[post("/public/audit/{id}/verify")]
+pub async fn verify_public_audit_report(
+    context: GeneralContext,
+    id: web::Path<String>,
+    report: web::Bytes,
+) -> error::Result<Json<ReportVerification>> {
+    Ok(Json(
+        AuditService::new(context)
+            .verify_report(id.parse()?, &report)
             .await?,
     ))
 }
@@ -6,7 +6,10 @@ use actix_web::{
 
 use common::{
     api::{
-        requests::{CreateRequest, PublicRequest},
+        requests::{
+            AssignRequest, CreateRequest, MyAuditRequestResult, PublicRequest, RequestFilter,
+            RequestOffer,
+        },
         seartch::PaginationParams,
     },
     context::GeneralContext,
@@ -44,10 +47,15 @@ pub async fn get_my_audit_request(
     context: GeneralContext,
     role: web::Path<Role>,
     pagination: Query<PaginationParams>,
-) -> error::Result<Json<Vec<PublicRequest>>> {
+    filter: Query<RequestFilter>,
+) -> error::Result<Json<MyAuditRequestResult>> {
     Ok(Json(
         RequestService::new(context)
-            .my_request(role.into_inner(), pagination.into_inner())
+            .my_request(
+                role.into_inner(),
+                pagination.into_inner(),
+                filter.into_inner(),
+            )
             .await?,
     ))
 }
@@ -65,6 +73,32 @@ pub async fn patch_audit_request(
     ))
 }
 
+#[patch("/audit_request/{id}/offer")]
+pub async fn patch_audit_request_offer(
+    context: GeneralContext,
+    id: web::Path<String>,
+    Json(data): Json<RequestOffer>,
+) -> error::Result<Json<PublicRequest>> {
+    Ok(Json(
+        RequestService::new(context)
+            .respond(id.parse()?, data)
+            .await?,
+    ))
+}
+
+#[post("/audit_request/{id}/assign")]
+pub async fn post_audit_request_assign(
+    context: GeneralContext,
+    id: web::Path<String>,
+    Json(data): Json<AssignRequest>,
+) -> error::Result<Json<PublicRequest>> {
+    Ok(Json(
+        RequestService::new(context)
+            .assign(id.parse()?, data)
+            .await?,
+    ))
+}
+
 #[delete("/audit_request/{id}")]
 pub async fn delete_audit_request(
     context: GeneralContext,
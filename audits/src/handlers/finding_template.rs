@@ -0,0 +1,69 @@
+use actix_web::{
+    delete, get, patch, post,
+    web::{self, Json},
+};
+
+use common::{
+    api::audits::{ChangeFindingTemplate, CreateFindingTemplate},
+    context::GeneralContext,
+    entities::finding_template::FindingTemplate,
+    error,
+};
+
+use crate::service::finding_template::FindingTemplateService;
+
+#[post("/finding_template")]
+pub async fn post_finding_template(
+    context: GeneralContext,
+    Json(data): Json<CreateFindingTemplate>,
+) -> error::Result<Json<FindingTemplate<String>>> {
+    Ok(Json(
+        FindingTemplateService::new(context).create(data).await?,
+    ))
+}
+
+#[get("/finding_template/{id}")]
+pub async fn get_finding_template(
+    context: GeneralContext,
+    id: web::Path<String>,
+) -> error::Result<Json<FindingTemplate<String>>> {
+    Ok(Json(
+        FindingTemplateService::new(context)
+            .find(id.parse()?)
+            .await?,
+    ))
+}
+
+#[get("/my_finding_template")]
+pub async fn get_my_finding_template(
+    context: GeneralContext,
+) -> error::Result<Json<Vec<FindingTemplate<String>>>> {
+    Ok(Json(
+        FindingTemplateService::new(context).my_templates().await?,
+    ))
+}
+
+#[patch("/finding_template/{id}")]
+pub async fn patch_finding_template(
+    context: GeneralContext,
+    id: web::Path<String>,
+    Json(data): Json<ChangeFindingTemplate>,
+) -> error::Result<Json<FindingTemplate<String>>> {
+    Ok(Json(
+        FindingTemplateService::new(context)
+            .change(id.parse()?, data)
+            .await?,
+    ))
+}
+
+#[delete("/finding_template/{id}")]
+pub async fn delete_finding_template(
+    context: GeneralContext,
+    id: web::Path<String>,
+) -> error::Result<Json<FindingTemplate<String>>> {
+    Ok(Json(
+        FindingTemplateService::new(context)
+            .delete(id.parse()?)
+            .await?,
+    ))
+}
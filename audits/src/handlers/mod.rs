@@ -1,2 +1,3 @@
 pub mod audit;
 pub mod audit_request;
+pub mod finding_template;
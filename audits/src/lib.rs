@@ -17,6 +17,10 @@ use common::context::effectfull_context::ServiceState;
 use common::services::API_PREFIX;
 pub use handlers::audit::*;
 pub use handlers::audit_request::*;
+pub use handlers::finding_template::*;
+
+/// Largest request body taken as raw bytes, which is the report PDF upload
+const MAX_PAYLOAD_SIZE: usize = 32 * 1024 * 1024;
 
 #[must_use]
 pub fn create_app(
@@ -37,26 +41,51 @@ pub fn create_app(
         .wrap(cors)
         .wrap(middleware::Logger::default())
         .app_data(web::Data::new(state))
+        .app_data(web::PayloadConfig::new(MAX_PAYLOAD_SIZE))
         .service(
             web::scope(&API_PREFIX)
                 .service(post_audit)
                 .service(get_audit)
                 .service(patch_audit)
                 .service(delete_audit)
+                .service(post_audit_cancel)
+                .service(post_audit_dispute)
+                .service(post_audit_dispute_resolve)
                 .service(post_audit_request)
                 .service(get_audit_request)
                 .service(patch_audit_request)
+                .service(patch_audit_request_offer)
+                .service(post_audit_request_assign)
                 .service(delete_audit_request)
                 .service(get_my_audit)
                 .service(get_my_audit_request)
                 .service(post_audit_issue)
+                .service(post_audit_issue_import)
+                .service(post_audit_issue_from_template)
                 .service(patch_audit_issue)
                 .service(get_audit_issue)
                 .service(get_audit_issue_by_id)
+                .service(get_audit_issues_export)
+                .service(get_audit_remediation)
+                .service(get_audit_timeline)
+                .service(get_audit_analytics)
+                .service(get_user_analytics)
                 .service(delete_audit_issue)
                 .service(patch_audit_disclose_all)
+                .service(post_audit_issue_comment)
+                .service(patch_audit_issue_comment)
+                .service(delete_audit_issue_comment)
+                .service(patch_audit_issue_comment_read)
                 .service(patch_audit_issue_read)
+                .service(post_finding_template)
+                .service(get_finding_template)
+                .service(get_my_finding_template)
+                .service(patch_finding_template)
+                .service(delete_finding_template)
+                .service(post_audit_review_round)
+                .service(patch_audit_review_round)
                 .service(get_public_audits)
+                .service(verify_public_audit_report)
                 .service(find_all_audit_request)
                 .service(post_no_customer_audit),
         );
@@ -1,18 +1,44 @@
 use std::env;
+use std::num::NonZeroU64;
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
+use actix_web::rt::{spawn, time};
 use actix_web::HttpServer;
 use audits::create_app;
 
 use audits::migrations::up_migrations;
+use audits::service::audit_request::RequestService;
+use audits::service::reminder::{ReminderOffsets, ReminderService};
 use common::auth::Service;
-use common::context::effectfull_context::ServiceState;
+use common::context::effectfull_context::{EffectfullContext, HandlerContext, ServiceState};
+use common::context::GeneralContext;
+use common::entities::activity::Activity;
 use common::entities::audit::Audit;
 use common::entities::audit_request::AuditRequest;
+use common::entities::finding_template::FindingTemplate;
 use common::repository::mongo_repository::MongoRepository;
 use common::verification::verify;
 use mongodb::bson::oid::ObjectId;
 
+/// Reads a number from the environment, a missing or invalid value falls back to `default`.
+fn env_or<T: FromStr + ToString>(name: &str, default: T) -> T {
+    let Ok(value) = env::var(name) else {
+        return default;
+    };
+
+    value.trim().parse().unwrap_or_else(|_| {
+        log::error!(
+            "Invalid value {:?} of {}, using {}",
+            value,
+            name,
+            default.to_string()
+        );
+        default
+    })
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     dotenv::dotenv().ok();
@@ -30,12 +56,78 @@ async fn main() -> std::io::Result<()> {
         MongoRepository::new(&mongo_uri, "audits", "audits").await;
     let audit_request_repo: MongoRepository<AuditRequest<ObjectId>> =
         MongoRepository::new(&mongo_uri, "audits", "requests").await;
+    let activity_repo: MongoRepository<Activity<ObjectId>> =
+        MongoRepository::new(&mongo_uri, "audits", "activity").await;
+    let finding_template_repo: MongoRepository<FindingTemplate<ObjectId>> =
+        MongoRepository::new(&mongo_uri, "audits", "finding_templates").await;
 
     let mut state = ServiceState::new(Service::Audits);
     state.insert(Arc::new(audit_repo));
     state.insert(Arc::new(audit_request_repo));
+    state.insert(Arc::new(finding_template_repo));
+    state.insert(Arc::new(activity_repo));
     let state = Arc::new(state);
 
+    // Zero is rejected like any other invalid value, `interval` panics on it
+    let timeout = env_or("REMINDER_TIMEOUT", NonZeroU64::new(3600).unwrap()).get();
+
+    let defaults = ReminderOffsets::default();
+    let offsets = ReminderOffsets {
+        start: env_or("REMINDER_START_OFFSET", defaults.start),
+        finish: env_or("REMINDER_FINISH_OFFSET", defaults.finish),
+    };
+
+    let sweep_timeout = env_or("REQUEST_SWEEP_TIMEOUT", NonZeroU64::new(3600).unwrap()).get();
+
+    // Requests without `expires_at` are kept, unless a maximum age is configured
+    let request_max_age = env::var("REQUEST_MAX_AGE").ok().and_then(|value| {
+        let max_age = value.trim().parse::<i64>().ok();
+        if max_age.is_none() {
+            log::error!("Invalid value {:?} of REQUEST_MAX_AGE, ignoring it", value);
+        }
+        max_age
+    });
+
+    let context = GeneralContext::Effectfull(EffectfullContext(
+        state.clone(),
+        HandlerContext {
+            user_auth: state.service_auth,
+        },
+    ));
+    spawn(async move {
+        let mut interval = time::interval(Duration::from_secs(timeout));
+        loop {
+            interval.tick().await;
+
+            if let Err(err) = ReminderService::new(context.clone())
+                .send_reminders(offsets)
+                .await
+            {
+                log::warn!("Failed to send reminders: {}", err);
+            }
+        }
+    });
+
+    let context = GeneralContext::Effectfull(EffectfullContext(
+        state.clone(),
+        HandlerContext {
+            user_auth: state.service_auth,
+        },
+    ));
+    spawn(async move {
+        let mut interval = time::interval(Duration::from_secs(sweep_timeout));
+        loop {
+            interval.tick().await;
+
+            if let Err(err) = RequestService::new(context.clone())
+                .expire_stale(request_max_age)
+                .await
+            {
+                log::warn!("Failed to expire requests: {}", err);
+            }
+        }
+    });
+
     HttpServer::new(move || create_app(state.clone()))
         .bind(("0.0.0.0", 3003))?
         .run()
@@ -151,15 +151,400 @@ impl Migration for IssuesChangeWillNotFixToNotFixed {
     }
 }
 
+pub struct IssueIdsFromCounter {}
+
+#[async_trait]
+impl Migration for IssueIdsFromCounter {
+    async fn up(&self, env: Env) -> anyhow::Result<()> {
+        let conn = env
+            .db
+            .expect("db is unavailable")
+            .collection::<Document>("audits");
+        use mongodb::error::Result;
+        let audits = conn
+            .find(None, None)
+            .await?
+            .collect::<Vec<Result<Document>>>()
+            .await;
+
+        for audit in audits {
+            let audit = audit?;
+            let id = audit.get_object_id("_id")?;
+
+            // Existing ids are kept, they are used in issue links and notifications.
+            let mut issues = audit.get_array("issues").cloned().unwrap_or_default();
+            let mut counter = issues
+                .iter()
+                .filter_map(|issue| match issue.as_document()?.get("id")? {
+                    Bson::Int32(id) => Some(*id as i64),
+                    Bson::Int64(id) => Some(*id),
+                    _ => None,
+                })
+                .max()
+                .unwrap_or(0);
+
+            for issue in issues.iter_mut() {
+                let issue = issue.as_document_mut().unwrap();
+                if !matches!(issue.get("id"), Some(Bson::Int32(_) | Bson::Int64(_))) {
+                    counter += 1;
+                    issue.insert("id", counter);
+                }
+            }
+
+            conn.update_one(
+                doc! {"_id": id},
+                doc! {"$set": {"issue_counter": counter, "issues": issues}},
+                None,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct NormalizeIssueSeverity {}
+
+fn normalize_severity(severity: &str) -> &'static str {
+    match severity.trim().to_ascii_lowercase().as_str() {
+        "critical" => "Critical",
+        "major" | "high" => "Major",
+        "medium" => "Medium",
+        "minor" | "low" => "Minor",
+        _ => "Informational",
+    }
+}
+
+#[async_trait]
+impl Migration for NormalizeIssueSeverity {
+    async fn up(&self, env: Env) -> anyhow::Result<()> {
+        let conn = env
+            .db
+            .expect("db is unavailable")
+            .collection::<Document>("audits");
+        use mongodb::error::Result;
+        let audits = conn
+            .find(None, None)
+            .await?
+            .collect::<Vec<Result<Document>>>()
+            .await;
+
+        for audit in audits {
+            let audit = audit?;
+            let id = audit.get_object_id("_id")?;
+
+            let mut issues = audit.get_array("issues").cloned().unwrap_or_default();
+            for issue in issues.iter_mut() {
+                let issue = issue.as_document_mut().unwrap();
+                let severity = normalize_severity(issue.get_str("severity").unwrap_or_default());
+                issue.insert("severity", severity);
+            }
+
+            conn.update_one(doc! {"_id": id}, doc! {"$set": {"issues": issues}}, None)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct IssueCommentsFromEvents {}
+
+// Comments used to be stored as issue events, with `read` counting the events seen by each user
+#[async_trait]
+impl Migration for IssueCommentsFromEvents {
+    async fn up(&self, env: Env) -> anyhow::Result<()> {
+        let conn = env
+            .db
+            .expect("db is unavailable")
+            .collection::<Document>("audits");
+        use mongodb::error::Result;
+        let audits = conn
+            .find(None, None)
+            .await?
+            .collect::<Vec<Result<Document>>>()
+            .await;
+
+        for audit in audits {
+            let audit = audit?;
+            let id = audit.get_object_id("_id")?;
+
+            let mut issues = audit.get_array("issues").cloned().unwrap_or_default();
+            for issue in issues.iter_mut() {
+                let issue = issue.as_document_mut().unwrap();
+
+                let read = issue.get_document("read").cloned().unwrap_or_default();
+                let events = issue.get_array("events").cloned().unwrap_or_default();
+                let mut comments = issue.get_array("comments").cloned().unwrap_or_default();
+
+                let mut remaining = Vec::new();
+                for (idx, event) in events.into_iter().enumerate() {
+                    let Some(mut event) = event.as_document().cloned() else {
+                        continue;
+                    };
+
+                    if event.get_str("kind").unwrap_or_default() != "Comment" {
+                        event.insert("id", remaining.len() as i64);
+                        remaining.push(Bson::Document(event));
+                        continue;
+                    }
+
+                    let timestamp = event.get("timestamp").cloned().unwrap_or(Bson::Int64(0));
+                    let mut comment_read = Document::new();
+                    for (user, count) in read.iter() {
+                        let count = match count {
+                            Bson::Int32(count) => *count as i64,
+                            Bson::Int64(count) => *count,
+                            _ => 0,
+                        };
+                        if count > idx as i64 {
+                            comment_read.insert(user, timestamp.clone());
+                        }
+                    }
+
+                    comments.push(Bson::Document(doc! {
+                        "id": ObjectId::new(),
+                        "parent": Bson::Null,
+                        "user": event.get("user").cloned().unwrap_or(Bson::Null),
+                        "text": event.get_str("message").unwrap_or_default(),
+                        "timestamp": timestamp,
+                        "edits": [],
+                        "deleted": false,
+                        "mentions": [],
+                        "read": comment_read,
+                    }));
+                }
+
+                issue.insert("events", remaining);
+                issue.insert("comments", comments);
+                issue.remove("read");
+            }
+
+            conn.update_one(doc! {"_id": id}, doc! {"$set": {"issues": issues}}, None)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct IssueStatusHistoryFromEvents {}
+
+// Status changes were only kept as "changed status to X" events
+#[async_trait]
+impl Migration for IssueStatusHistoryFromEvents {
+    async fn up(&self, env: Env) -> anyhow::Result<()> {
+        let conn = env
+            .db
+            .expect("db is unavailable")
+            .collection::<Document>("audits");
+        use mongodb::error::Result;
+        let audits = conn
+            .find(None, None)
+            .await?
+            .collect::<Vec<Result<Document>>>()
+            .await;
+
+        for audit in audits {
+            let audit = audit?;
+            let id = audit.get_object_id("_id")?;
+
+            let mut issues = audit.get_array("issues").cloned().unwrap_or_default();
+            for issue in issues.iter_mut() {
+                let issue = issue.as_document_mut().unwrap();
+
+                if issue
+                    .get_array("status_history")
+                    .is_ok_and(|history| !history.is_empty())
+                {
+                    continue;
+                }
+
+                let events = issue.get_array("events").cloned().unwrap_or_default();
+                let history: Vec<Bson> = events
+                    .iter()
+                    .filter_map(|event| event.as_document())
+                    .filter(|event| event.get_str("kind").unwrap_or_default() == "StatusChange")
+                    .filter_map(|event| {
+                        let status = event
+                            .get_str("message")
+                            .ok()?
+                            .strip_prefix("changed status to ")?;
+                        let timestamp = event.get("timestamp")?.clone();
+                        Some(Bson::Document(
+                            doc! {"status": status, "timestamp": timestamp},
+                        ))
+                    })
+                    .collect();
+
+                issue.insert("status_history", history);
+            }
+
+            conn.update_one(doc! {"_id": id}, doc! {"$set": {"issues": issues}}, None)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct RequestOffersFromFields {}
+
+// Requests only kept the latest terms in price, time and last_changer
+#[async_trait]
+impl Migration for RequestOffersFromFields {
+    async fn up(&self, env: Env) -> anyhow::Result<()> {
+        let conn = env
+            .db
+            .expect("db is unavailable")
+            .collection::<Document>("requests");
+        use mongodb::error::Result;
+        let requests = conn
+            .find(None, None)
+            .await?
+            .collect::<Vec<Result<Document>>>()
+            .await;
+
+        for request in requests {
+            let request = request?;
+            let id = request.get_object_id("_id")?;
+
+            if request
+                .get_array("offers")
+                .is_ok_and(|offers| !offers.is_empty())
+            {
+                continue;
+            }
+
+            let role = request.get_str("last_changer").unwrap_or("Customer");
+            let author = if role == "Auditor" {
+                request.get("auditor_id")
+            } else {
+                request.get("customer_id")
+            };
+            let (Some(author), Some(price), Some(time)) =
+                (author, request.get("price"), request.get("time"))
+            else {
+                continue;
+            };
+            let timestamp = request.get_i64("last_modified").unwrap_or_default() / 1_000_000;
+
+            let offer = doc! {
+                "author": author.clone(),
+                "role": role,
+                "price": price.clone(),
+                "time": time.clone(),
+                "scope": [],
+                "message": "",
+                "status": "Pending",
+                "timestamp": timestamp,
+            };
+
+            conn.update_one(doc! {"_id": id}, doc! {"$set": {"offers": [offer]}}, None)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct EventIdsFromCounter {}
+
+// Event ids used to be positions in the events of each issue
+#[async_trait]
+impl Migration for EventIdsFromCounter {
+    async fn up(&self, env: Env) -> anyhow::Result<()> {
+        let conn = env
+            .db
+            .expect("db is unavailable")
+            .collection::<Document>("audits");
+        use mongodb::error::Result;
+        let audits = conn
+            .find(None, None)
+            .await?
+            .collect::<Vec<Result<Document>>>()
+            .await;
+
+        for audit in audits {
+            let audit = audit?;
+            let id = audit.get_object_id("_id")?;
+
+            let issues = audit.get_array("issues").cloned().unwrap_or_default();
+            let counter = issues
+                .iter()
+                .filter_map(|issue| issue.as_document()?.get_array("events").ok())
+                .flatten()
+                .filter_map(|event| match event.as_document()?.get("id")? {
+                    Bson::Int32(id) => Some(*id as i64),
+                    Bson::Int64(id) => Some(*id),
+                    _ => None,
+                })
+                .max()
+                .unwrap_or(0);
+
+            conn.update_one(
+                doc! {"_id": id},
+                doc! {"$set": {"event_counter": counter}},
+                None,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct RequestExpiryInMillis {}
+
+// `expires_at` of requests used to be in microseconds
+#[async_trait]
+impl Migration for RequestExpiryInMillis {
+    async fn up(&self, env: Env) -> anyhow::Result<()> {
+        let conn = env
+            .db
+            .expect("db is unavailable")
+            .collection::<Document>("requests");
+        use mongodb::error::Result;
+        let requests = conn
+            .find(doc! {"expires_at": {"$type": "long"}}, None)
+            .await?
+            .collect::<Vec<Result<Document>>>()
+            .await;
+
+        for request in requests {
+            let request = request?;
+            let id = request.get_object_id("_id")?;
+            let expires_at = request.get_i64("expires_at")?;
+
+            conn.update_one(
+                doc! {"_id": id},
+                doc! {"$set": {"expires_at": expires_at / 1000}},
+                None,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
 pub async fn up_migrations(mongo_uri: &str) -> anyhow::Result<()> {
     let client = Client::with_uri_str(mongo_uri).await.unwrap();
     let db = client.database("audits");
 
+    // Severities are normalized first, the migrations below deserialize whole audits
     let migrations: Vec<Box<dyn Migration>> = vec![
+        Box::new(NormalizeIssueSeverity {}),
         Box::new(NewAuditStatusMigration {}),
         Box::new(SecondAttemptToMutateStatus {}),
         Box::new(AuditStatusCorrection {}),
         Box::new(IssuesChangeWillNotFixToNotFixed {}),
+        Box::new(IssueIdsFromCounter {}),
+        Box::new(IssueCommentsFromEvents {}),
+        Box::new(IssueStatusHistoryFromEvents {}),
+        Box::new(RequestOffersFromFields {}),
+        Box::new(EventIdsFromCounter {}),
+        Box::new(RequestExpiryInMillis {}),
     ];
     mongodb_migrator::migrator::default::DefaultMigrator::new()
         .with_conn(db.clone())
@@ -0,0 +1,146 @@
+use chrono::Utc;
+use common::{
+    access_rules::{AccessRules, Read},
+    api::{audits::Timeline, seartch::PaginationParams},
+    context::GeneralContext,
+    entities::{
+        activity::{Activity, ActivityKind},
+        audit::Audit,
+        audit_request::AuditRequest,
+        issue::{Issue, Status},
+    },
+    error::{self, AddCode},
+};
+use mongodb::bson::{doc, oid::ObjectId, Bson};
+
+pub struct ActivityService {
+    context: GeneralContext,
+}
+
+impl ActivityService {
+    #[must_use]
+    pub const fn new(context: GeneralContext) -> Self {
+        Self { context }
+    }
+
+    /// The change is already saved when its activity is recorded, so a failed insert
+    /// is logged instead of failing the request.
+    async fn insert(
+        &self,
+        audit_id: ObjectId,
+        kind: ActivityKind,
+        message: String,
+        issue_id: Option<usize>,
+        auditor_only: bool,
+    ) {
+        if let Err(err) = self
+            .try_insert(audit_id, kind, message, issue_id, auditor_only)
+            .await
+        {
+            log::warn!("Failed to record activity of audit {}: {}", audit_id, err);
+        }
+    }
+
+    async fn try_insert(
+        &self,
+        audit_id: ObjectId,
+        kind: ActivityKind,
+        message: String,
+        issue_id: Option<usize>,
+        auditor_only: bool,
+    ) -> error::Result<()> {
+        let activities = self.context.try_get_repository::<Activity<ObjectId>>()?;
+
+        let activity = Activity {
+            id: ObjectId::new(),
+            audit_id,
+            user: self.context.auth().id(),
+            kind,
+            message,
+            issue_id,
+            auditor_only,
+            timestamp: Utc::now().timestamp(),
+        };
+
+        activities.insert(&activity).await?;
+
+        Ok(())
+    }
+
+    pub async fn record(&self, audit_id: ObjectId, kind: ActivityKind, message: impl Into<String>) {
+        self.insert(audit_id, kind, message.into(), None, false)
+            .await
+    }
+
+    /// Records activity that only the auditor can see.
+    pub async fn record_private(
+        &self,
+        audit_id: ObjectId,
+        kind: ActivityKind,
+        message: impl Into<String>,
+    ) {
+        self.insert(audit_id, kind, message.into(), None, true)
+            .await
+    }
+
+    pub async fn record_issue(
+        &self,
+        audit_id: ObjectId,
+        issue: &Issue<ObjectId>,
+        kind: ActivityKind,
+        message: impl Into<String>,
+    ) {
+        let auditor_only = issue.status == Status::Draft;
+        self.insert(audit_id, kind, message.into(), Some(issue.id), auditor_only)
+            .await
+    }
+
+    /// Activity of the audit and of the request it was created from, oldest first.
+    pub async fn timeline(
+        &self,
+        audit_id: ObjectId,
+        pagination: PaginationParams,
+    ) -> error::Result<Timeline> {
+        let auth = self.context.auth();
+
+        let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
+        let requests = self
+            .context
+            .try_get_repository::<AuditRequest<ObjectId>>()?;
+
+        let is_customer = if let Some(audit) = audits.find("_id", &Bson::ObjectId(audit_id)).await?
+        {
+            if !Read.get_access(&auth, &audit) {
+                return Err(anyhow::anyhow!("User is not available to read this audit").code(403));
+            }
+            auth.id() == Some(audit.customer_id) && !audit.no_customer
+        } else if let Some(request) = requests.find("id", &Bson::ObjectId(audit_id)).await? {
+            if !Read.get_access(&auth, &request) {
+                return Err(anyhow::anyhow!("User is not available to read this audit").code(403));
+            }
+            auth.id() == Some(request.customer_id)
+        } else {
+            return Err(anyhow::anyhow!("No audit found").code(404));
+        };
+
+        let activities = self.context.try_get_repository::<Activity<ObjectId>>()?;
+
+        let mut filter = doc! {"audit_id": audit_id};
+        if is_customer {
+            filter.insert("auditor_only", false);
+        }
+
+        let (skip, limit) = pagination.skip_limit(50)?;
+
+        let (activities, total_documents) = activities
+            .find_page(filter, doc! {"timestamp": 1}, skip, limit)
+            .await?;
+
+        let result = activities.into_iter().map(Activity::stringify).collect();
+
+        Ok(Timeline {
+            result,
+            total_documents,
+        })
+    }
+}
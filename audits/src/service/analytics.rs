@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use common::{
+    access_rules::{AccessRules, Read},
+    api::{
+        analytics::{AuditAnalytics, StatisticsBuilder, UserAnalytics},
+        audits::participant_filter,
+        project::request_projects,
+    },
+    auth::Auth,
+    context::GeneralContext,
+    entities::{audit::Audit, role::Role},
+    error::{self, AddCode},
+};
+use mongodb::bson::{doc, oid::ObjectId, Bson};
+
+use super::audit::AuditService;
+
+const PAGE_SIZE: i32 = 100;
+
+pub struct AnalyticsService {
+    context: GeneralContext,
+}
+
+impl AnalyticsService {
+    #[must_use]
+    pub const fn new(context: GeneralContext) -> Self {
+        Self { context }
+    }
+
+    fn audit_analytics(audit: &Audit<ObjectId>, project_name: String) -> AuditAnalytics {
+        let mut statistics = StatisticsBuilder::default();
+        statistics.add_audit(audit);
+
+        AuditAnalytics {
+            audit_id: audit.id.to_hex(),
+            project_name,
+            statistics: statistics.build(),
+        }
+    }
+
+    pub async fn audit(&self, audit_id: ObjectId) -> error::Result<AuditAnalytics> {
+        let auth = self.context.auth();
+
+        let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
+
+        let Some(audit) = audits.find("_id", &Bson::ObjectId(audit_id)).await? else {
+            return Err(anyhow::anyhow!("No audit found").code(404));
+        };
+
+        if !Read.get_access(&auth, &audit) {
+            return Err(anyhow::anyhow!("User is not available to read this audit").code(403));
+        }
+
+        let project_name = AuditService::new(self.context.clone())
+            .project_name(&audit)
+            .await?;
+
+        Ok(Self::audit_analytics(&audit, project_name))
+    }
+
+    pub async fn user(&self, role: Role, user_id: ObjectId) -> error::Result<UserAnalytics> {
+        let auth = self.context.auth();
+
+        let allowed = match auth {
+            Auth::Service(_, _) | Auth::Admin(_) => true,
+            Auth::User(id) => id == user_id,
+            Auth::None => false,
+        };
+
+        if !allowed {
+            return Err(anyhow::anyhow!("User is not available to read these analytics").code(403));
+        }
+
+        let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
+
+        // Team members count as auditors of the audit
+        let filter = participant_filter(role, user_id);
+        let mut found = Vec::new();
+
+        loop {
+            let (page, total) = audits
+                .find_page(
+                    filter.clone(),
+                    doc! {"_id": 1},
+                    found.len() as i32,
+                    PAGE_SIZE,
+                )
+                .await?;
+
+            let last_page = page.is_empty();
+            found.extend(page);
+            if last_page || found.len() as u64 >= total {
+                break;
+            }
+        }
+
+        let mut audits = found;
+
+        // Audits without a customer belong to the auditor only
+        if role == Role::Customer {
+            audits.retain(|audit| !audit.no_customer);
+        }
+
+        // Project names are fetched in one request, like in `PublicAudit::new_many`
+        let mut project_ids = Vec::new();
+        for audit in &audits {
+            if audit.project_name.is_empty()
+                && !audit.no_customer
+                && !project_ids.contains(&audit.project_id)
+            {
+                project_ids.push(audit.project_id);
+            }
+        }
+
+        let project_names: HashMap<String, String> =
+            request_projects(&self.context, project_ids, self.context.server_auth())
+                .await?
+                .into_iter()
+                .map(|project| (project.id, project.name))
+                .collect();
+
+        let mut statistics = StatisticsBuilder::default();
+        let mut per_audit = Vec::new();
+
+        for audit in &audits {
+            let project_name = if audit.project_name.is_empty() {
+                project_names
+                    .get(&audit.project_id.to_hex())
+                    .cloned()
+                    .unwrap_or_default()
+            } else {
+                audit.project_name.clone()
+            };
+
+            statistics.add_audit(audit);
+            per_audit.push(Self::audit_analytics(audit, project_name));
+        }
+
+        Ok(UserAnalytics {
+            user_id: user_id.to_hex(),
+            role,
+            audits: audits.len(),
+            statistics: statistics.build(),
+            per_audit,
+        })
+    }
+}
@@ -1,33 +1,50 @@
-use std::collections::HashMap;
+use chrono::{NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
-use chrono::Utc;
-use rand::Rng;
+use std::collections::HashMap;
 
 use common::api::audits::NoCustomerAuditRequest;
 use common::entities::audit_request::TimeRange;
 
 use common::{
-    access_rules::{AccessRules, Edit, Read},
+    access_rules::{AccessRules, Delete, Disclose, Edit, Read},
     api::{
-        audits::{AuditAction, AuditChange, CreateIssue, PublicAudit},
+        audits::{
+            AuditChange, AuditFilter, ChangeReviewRound, CreateIssue, CreateIssueFromTemplate,
+            CreateReviewRound, ExportFormat, ImportParams, ImportSummary, PublicAudit,
+            RemediationItem, RemediationSummary, ReportVerification, ResolveDispute,
+        },
+        cloc::request_lines_of_code,
         events::{post_event, EventPayload, PublicEvent},
-        issue::PublicIssue,
-        send_notification, NewNotification,
-        seartch::PaginationParams,
+        issue::{ChangeComment, CreateComment, PublicComment, PublicIssue},
+        seartch::{PaginationParams, MAX_PER_PAGE},
+        send_notification,
+        user::request_users,
+        NewNotification,
     },
+    auth::Auth,
     context::GeneralContext,
     entities::{
-        audit::{Audit, AuditStatus},
-        audit_request::AuditRequest,
-        issue::{severity_to_integer, ChangeIssue, Event, EventKind, Issue, Status, Action},
+        activity::ActivityKind,
+        audit::{report_digest, Audit, AuditAction, AuditStatus, AuditTransition, TeamMember},
+        audit_request::{AuditRequest, OfferStatus},
+        cvss::Cvss,
+        finding_template::substitute,
+        issue::{
+            issue_code, Action, ChangeIssue, CodeLocation, Comment, CommentEdit, Event, EventKind,
+            Issue, IssueTransition, Status,
+        },
         project::get_project,
+        review_round::ReviewRound,
         role::Role,
     },
     error::{self, AddCode},
 };
-use mongodb::bson::{oid::ObjectId, Bson};
+use mongodb::bson::{doc, oid::ObjectId, to_bson, to_document, Bson, Document};
 
-use super::audit_request::PublicRequest;
+use super::{
+    activity::ActivityService, audit_request::PublicRequest, export,
+    finding_template::FindingTemplateService, import::parse_report,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MyAuditResult {
@@ -52,58 +69,87 @@ impl AuditService {
         let auditor_id = request.auditor_id.parse()?;
         let customer_id = request.customer_id.parse()?;
 
-        let tags = if let Some(request_tags) = request.tags {
-            request_tags
-        } else {
-            Vec::new()
-        };
+        let tags = request.tags.unwrap_or_default();
+
+        let requests = self
+            .context
+            .try_get_repository::<AuditRequest<ObjectId>>()?;
+
+        let stored = requests
+            .find("id", &Bson::ObjectId(request.id.parse()?))
+            .await?;
+
+        let mut price = request.price;
+        let mut time = request.time;
+        let mut scope = request.project_scope;
+
+        if let Some(offer) = stored.as_ref().and_then(AuditRequest::latest_offer) {
+            if offer.status == OfferStatus::Rejected {
+                return Err(anyhow::anyhow!("The latest offer was rejected").code(400));
+            }
+
+            if offer.status == OfferStatus::Pending && auth.id() == Some(offer.author) {
+                return Err(anyhow::anyhow!("Own offer can't be accepted").code(400));
+            }
+
+            price = offer.price;
+            time = offer.time.clone();
+            if !offer.scope.is_empty() {
+                scope = offer.scope.clone();
+            }
+        }
+
+        let team = stored.map(|stored| stored.team).unwrap_or_default();
 
         let audit = Audit {
             id: request.id.parse()?,
             customer_id,
             auditor_id,
+            team,
             project_id: request.project_id.parse()?,
             project_name: request.project_name,
             description: request.description,
             status: AuditStatus::Waiting,
-            scope: request.project_scope,
+            status_history: Vec::new(),
+            scope,
             tags,
-            price: request.price,
+            price,
             last_modified: Utc::now().timestamp_micros(),
             report: None,
             report_name: None,
-            time: request.time,
+            report_sha256: None,
+            time,
             issues: Vec::new(),
+            issue_counter: 0,
+            event_counter: 0,
+            review_rounds: Vec::new(),
+            reminders: Vec::new(),
+            lines_of_code: None,
             public: false,
             no_customer: false,
         };
 
         audits.insert(&audit).await?;
 
-        let requests = self
-            .context
-            .try_get_repository::<AuditRequest<ObjectId>>()?;
-
         requests.delete("id", &request.id.parse()?).await?;
 
-        let public_audit = PublicAudit::new(&self.context, audit).await?;
+        self.activity()
+            .record(audit.id, ActivityKind::AuditCreated, "accepted the request")
+            .await;
 
-        let event_reciver = if auth.id().unwrap() == customer_id {
-            auditor_id
-        } else {
-            customer_id
-        };
+        let receivers = Self::participants_except(&audit, auth.id());
+
+        let public_audit = PublicAudit::new(&self.context, audit).await?;
 
-        let event = PublicEvent::new(event_reciver, EventPayload::NewAudit(public_audit.clone()));
+        for receiver in receivers {
+            let event = PublicEvent::new(receiver, EventPayload::NewAudit(public_audit.clone()));
 
-        post_event(&self.context, event, self.context.server_auth()).await?;
+            post_event(&self.context, event, self.context.server_auth()).await?;
 
-        let event = PublicEvent::new(
-            event_reciver,
-            EventPayload::RequestAccept(request.id.clone()),
-        );
+            let event = PublicEvent::new(receiver, EventPayload::RequestAccept(request.id.clone()));
 
-        post_event(&self.context, event, self.context.server_auth()).await?;
+            post_event(&self.context, event, self.context.server_auth()).await?;
+        }
 
         Ok(public_audit)
     }
@@ -123,24 +169,38 @@ impl AuditService {
             to: Utc::now().timestamp_micros(),
         };
 
+        for location in request.issues.iter().flat_map(|issue| &issue.locations) {
+            location.validate(&request.scope)?;
+        }
+
+        let issues = CreateIssue::to_issue_map(request.issues)?;
+
         let audit = Audit {
             id: ObjectId::new(),
             customer_id,
             auditor_id,
+            team: Vec::new(),
             project_id: ObjectId::new(),
             project_name: request.project_name,
             description: request.description,
             status: request.status,
+            status_history: Vec::new(),
             scope: request.scope,
             tags: request.tags,
             price: 0,
             last_modified: Utc::now().timestamp_micros(),
             report: None,
             report_name: None,
+            report_sha256: None,
             time,
             public: false,
             no_customer: true,
-            issues: CreateIssue::to_issue_map(request.issues),
+            issue_counter: issues.len(),
+            event_counter: 0,
+            review_rounds: Vec::new(),
+            reminders: Vec::new(),
+            lines_of_code: None,
+            issues,
         };
 
         if !Edit.get_access(&auth, &audit) {
@@ -149,7 +209,121 @@ impl AuditService {
 
         audits.insert(&audit).await?;
 
-        Ok(PublicAudit::new(&self.context, audit).await?)
+        self.activity()
+            .record(audit.id, ActivityKind::AuditCreated, "created the audit")
+            .await;
+
+        PublicAudit::new(&self.context, audit).await
+    }
+
+    fn activity(&self) -> ActivityService {
+        ActivityService::new(self.context.clone())
+    }
+
+    async fn record_transition(&self, audit: &Audit<ObjectId>) {
+        let Some(transition) = audit.status_history.last() else {
+            return;
+        };
+
+        let mut message = format!(
+            "changed status from {:?} to {:?}",
+            transition.from, transition.to
+        );
+
+        if let Some(reason) = &transition.reason {
+            message.push_str(&format!(": {}", reason));
+        }
+
+        self.activity()
+            .record(audit.id, ActivityKind::StatusChanged, message)
+            .await
+    }
+
+    async fn save_audit(&self, audit: &mut Audit<ObjectId>) -> error::Result<()> {
+        let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
+
+        let version = audit.last_modified;
+        audit.last_modified = Utc::now().timestamp_micros().max(version + 1);
+
+        if !audits.replace("_id", &audit.id, version, audit).await? {
+            return Err(anyhow::anyhow!(
+                "Audit was changed by another user, reload it and try again"
+            )
+            .code(409));
+        }
+
+        Ok(())
+    }
+
+    /// Sets only the `changed` fields of the issue and appends the new events,
+    /// so concurrent changes of other fields are kept.
+    async fn save_issue(
+        &self,
+        audit_id: ObjectId,
+        issue: &Issue<ObjectId>,
+        changed: &[&str],
+        new_events: &[Event<ObjectId>],
+    ) -> error::Result<()> {
+        let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
+
+        let mut document = to_document(issue)?;
+        let mut set = doc! {
+            "last_modified": Utc::now().timestamp_micros(),
+            "issues.$.last_modified": issue.last_modified,
+        };
+        for field in changed {
+            if let Some(value) = document.remove(*field) {
+                set.insert(format!("issues.$.{}", field), value);
+            }
+        }
+
+        let mut push = doc! {};
+        if !new_events.is_empty() {
+            push.insert("issues.$.events", doc! {"$each": to_bson(new_events)?});
+        }
+        if changed.contains(&"status") {
+            if let Some(transition) = issue.status_history.last() {
+                push.insert("issues.$.status_history", to_bson(transition)?);
+            }
+        }
+
+        let issue_id = Bson::Int64(issue.id as i64);
+        if !audits
+            .update_element("_id", &audit_id, "issues", &issue_id, set, push)
+            .await?
+        {
+            return Err(anyhow::anyhow!("No issue found").code(404));
+        }
+
+        Ok(())
+    }
+
+    /// Gives new events ids from the `event_counter` of the audit, so concurrent
+    /// changes of an issue never reuse one.
+    async fn number_events(
+        &self,
+        audit_id: ObjectId,
+        events: Vec<&mut Event<ObjectId>>,
+    ) -> error::Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
+
+        let Some(last_id) = audits
+            .increment("_id", &audit_id, "event_counter", events.len() as i64)
+            .await?
+        else {
+            return Err(anyhow::anyhow!("No audit found").code(404));
+        };
+
+        let first_id = last_id as usize + 1 - events.len();
+        for (idx, event) in events.into_iter().enumerate() {
+            event.id = first_id + idx;
+        }
+
+        Ok(())
     }
 
     async fn get_audit(&self, id: ObjectId) -> error::Result<Option<Audit<ObjectId>>> {
@@ -183,49 +357,34 @@ impl AuditService {
     pub async fn my_audit(
         &self,
         role: Role,
-        pagination: PaginationParams
-    ) -> error::Result<Vec<PublicAudit>> {
-        let page = pagination.page.unwrap_or(0);
-        let per_page = pagination.per_page.unwrap_or(0);
-        let limit = pagination.per_page.unwrap_or(1000);
-        let skip = (page - 1) * per_page;
+        pagination: PaginationParams,
+        filter: AuditFilter,
+    ) -> error::Result<MyAuditResult> {
+        let (skip, limit) = pagination.skip_limit(MAX_PER_PAGE)?;
 
         let auth = self.context.auth();
 
         let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
 
-        let (audits, _total_documents) = match role {
-            Role::Auditor => {
-                audits
-                    .find_many_limit(
-                        "auditor_id",
-                        &Bson::ObjectId(auth.id().unwrap()),
-                        skip,
-                        limit,
-                    ).await?
-            }
-            Role::Customer => {
-                audits
-                    .find_many_limit(
-                        "customer_id",
-                        &Bson::ObjectId(auth.id().unwrap()),
-                        skip,
-                        limit,
-                    ).await?
-            }
+        let Some(user_id) = auth.id() else {
+            return Err(anyhow::anyhow!("No user found").code(403));
         };
 
-        let mut public_audits = Vec::new();
-
-        for audit in audits {
-            public_audits.push(PublicAudit::new(&self.context, audit).await?);
-        }
-
-        // Ok(MyAuditResult {
-        //     result: public_audits,
-        //     total_documents,
-        // })
-        Ok(public_audits)
+        let (audits, total_documents) = audits
+            .find_page(
+                filter.to_document(role, user_id)?,
+                filter.sort(),
+                skip,
+                limit,
+            )
+            .await?;
+
+        let public_audits = PublicAudit::new_many(&self.context, audits).await?;
+
+        Ok(MyAuditResult {
+            result: public_audits,
+            total_documents,
+        })
     }
 
     pub async fn change(&self, id: ObjectId, change: AuditChange) -> error::Result<PublicAudit> {
@@ -241,71 +400,255 @@ impl AuditService {
             return Err(anyhow::anyhow!("User is not available to change this audit").code(403));
         }
 
+        let mut changed = Vec::new();
+
+        if let Some(team) = change.team {
+            if matches!(auth, Auth::User(id) if id != audit.auditor_id) {
+                return Err(anyhow::anyhow!("Only the lead auditor can change the team").code(403));
+            }
+
+            audit.team = TeamMember::parse_team(team, audit.auditor_id, audit.customer_id)?;
+            changed.push("team");
+        }
+
         if let Some(public) = change.public {
             audit.public = public;
+            changed.push("visibility");
         }
 
-        if audit.status != AuditStatus::Resolved || audit.no_customer {
+        if !(audit.status.is_final() || audit.status.is_frozen()) || audit.no_customer {
             if let Some(scope) = change.scope {
                 audit.scope = scope;
+                changed.push("scope");
             }
             if let Some(description) = change.description {
                 audit.description = description;
+                changed.push("description");
             }
             if let Some(tags) = change.tags {
                 audit.tags = tags;
+                changed.push("tags");
             }
         }
 
         if audit.no_customer {
             if let Some(project_name) = change.project_name {
                 audit.project_name = project_name;
+                changed.push("project name");
             }
         }
 
         if let Some(report) = change.report {
             audit.report = Some(report);
+            changed.push("report");
+
+            if audit.status == AuditStatus::Resolved {
+                audit.hash_report(&self.context).await;
+            }
         }
 
         if let Some(report_name) = change.report_name {
             audit.report_name = Some(report_name);
         }
 
-        if let Some(action) = change.action {
-            match action {
-                AuditAction::Start => {
-                    if audit.status == AuditStatus::Waiting {
-                        audit.status = AuditStatus::Started;
-                    }
-                }
-                AuditAction::Resolve => {
-                    if audit.status == AuditStatus::Started {
-                        audit.status = AuditStatus::Resolved;
-                        audit.resolve(&self.context).await;
-                    }
-                }
+        let action_applied = if let Some(action) = change.action {
+            if matches!(action, AuditAction::Cancel | AuditAction::Dispute) {
+                return Err(anyhow::anyhow!("Action {:?} requires a reason", action).code(400));
             }
+
+            self.apply_action(&mut audit, action, None).await?;
+            true
+        } else {
+            false
+        };
+
+        self.save_audit(&mut audit).await?;
+
+        if !changed.is_empty() {
+            let message = format!("changed {}", changed.join(", "));
+            self.activity()
+                .record(audit.id, ActivityKind::AuditChanged, message)
+                .await;
         }
 
-        audit.last_modified = Utc::now().timestamp_micros();
+        if action_applied {
+            self.record_transition(&audit).await;
+        }
 
-        audits.delete("_id", &id).await?;
-        audits.insert(&audit).await?;
+        let receivers = Self::participants_except(&audit, auth.id());
 
-        let event_reciver = if auth.id().unwrap() == audit.customer_id {
-            audit.auditor_id
-        } else {
-            audit.customer_id
+        let public_audit = PublicAudit::new(&self.context, audit).await?;
+
+        for receiver in receivers {
+            let event = PublicEvent::new(receiver, EventPayload::AuditUpdate(public_audit.clone()));
+
+            post_event(&self.context, event, self.context.server_auth()).await?;
+        }
+
+        Ok(public_audit)
+    }
+
+    async fn apply_action(
+        &self,
+        audit: &mut Audit<ObjectId>,
+        action: AuditAction,
+        reason: Option<String>,
+    ) -> error::Result<()> {
+        let auth = self.context.auth();
+
+        let Some(new_status) = audit.status.apply(&action) else {
+            return Err(anyhow::anyhow!(
+                "Action {:?} is not allowed for audit in status {:?}",
+                action,
+                audit.status
+            )
+            .code(400));
+        };
+
+        if let Auth::User(user_id) = auth {
+            if audit.status == AuditStatus::Disputed {
+                return Err(anyhow::anyhow!("Dispute can only be resolved by an admin").code(403));
+            }
+
+            let allowed = audit.no_customer
+                || (audit.is_editor(user_id) && action.is_auditor())
+                || (user_id == audit.customer_id && action.is_customer());
+
+            if !allowed {
+                return Err(
+                    anyhow::anyhow!("User is not available to {:?} this audit", action).code(403),
+                );
+            }
+        }
+
+        // Counted once, when the work on the scope begins
+        if action == AuditAction::Start && audit.lines_of_code.is_none() {
+            audit.lines_of_code = request_lines_of_code(&self.context, &audit.scope).await;
+        }
+
+        audit.status_history.push(AuditTransition {
+            from: audit.status.clone(),
+            to: new_status.clone(),
+            action,
+            user: auth.id(),
+            timestamp: Utc::now().timestamp_micros(),
+            reason,
+        });
+
+        audit.status = new_status;
+
+        if audit.status == AuditStatus::Resolved {
+            audit.resolve(&self.context).await;
+        }
+
+        Ok(())
+    }
+
+    pub async fn cancel(&self, id: ObjectId, reason: String) -> error::Result<PublicAudit> {
+        self.transition(id, AuditAction::Cancel, reason, false)
+            .await
+    }
+
+    pub async fn dispute(&self, id: ObjectId, reason: String) -> error::Result<PublicAudit> {
+        self.transition(id, AuditAction::Dispute, reason, false)
+            .await
+    }
+
+    pub async fn resolve_dispute(
+        &self,
+        id: ObjectId,
+        resolution: ResolveDispute,
+    ) -> error::Result<PublicAudit> {
+        let Auth::Admin(_) = self.context.auth() else {
+            return Err(anyhow::anyhow!("Only an admin can resolve a dispute").code(403));
+        };
+
+        self.transition(id, resolution.action, resolution.reason, true)
+            .await
+    }
+
+    async fn transition(
+        &self,
+        id: ObjectId,
+        action: AuditAction,
+        reason: String,
+        resolve_dispute: bool,
+    ) -> error::Result<PublicAudit> {
+        let auth = self.context.auth();
+
+        let Some(mut audit) = self.get_audit(id).await? else {
+            return Err(anyhow::anyhow!("No audit found").code(404));
         };
 
+        if !Edit.get_access(&auth, &audit) {
+            return Err(anyhow::anyhow!("User is not available to change this audit").code(403));
+        }
+
+        if reason.trim().is_empty() {
+            return Err(anyhow::anyhow!("Reason is required").code(400));
+        }
+
+        if resolve_dispute && audit.status != AuditStatus::Disputed {
+            return Err(anyhow::anyhow!("Audit is not disputed").code(400));
+        }
+
+        self.apply_action(&mut audit, action, Some(reason.clone()))
+            .await?;
+
+        self.save_audit(&mut audit).await?;
+        self.record_transition(&audit).await;
+
+        let mut receivers: Vec<_> = audit
+            .auditors()
+            .into_iter()
+            .map(|auditor_id| (auditor_id, Role::Auditor))
+            .collect();
+        if !audit.no_customer {
+            receivers.push((audit.customer_id, Role::Customer));
+        }
+        receivers.retain(|(receiver_id, _)| Some(*receiver_id) != auth.id());
+
+        let status = audit.status.clone();
         let public_audit = PublicAudit::new(&self.context, audit).await?;
 
-        let event = PublicEvent::new(
-            event_reciver,
-            EventPayload::AuditUpdate(public_audit.clone()),
-        );
+        for (receiver_id, role) in receivers {
+            let template = match (resolve_dispute, &status, role) {
+                (true, _, Role::Auditor) => {
+                    include_str!("../../templates/audit_dispute_resolved_auditor.txt")
+                }
+                (true, _, Role::Customer) => {
+                    include_str!("../../templates/audit_dispute_resolved_customer.txt")
+                }
+                (false, AuditStatus::Disputed, Role::Auditor) => {
+                    include_str!("../../templates/audit_disputed_auditor.txt")
+                }
+                (false, AuditStatus::Disputed, Role::Customer) => {
+                    include_str!("../../templates/audit_disputed_customer.txt")
+                }
+                (false, _, Role::Auditor) => {
+                    include_str!("../../templates/audit_canceled_auditor.txt")
+                }
+                (false, _, Role::Customer) => {
+                    include_str!("../../templates/audit_canceled_customer.txt")
+                }
+            };
 
-        post_event(&self.context, event, self.context.server_auth()).await?;
+            let mut new_notification: NewNotification = serde_json::from_str(template)?;
+            new_notification.user_id = Some(receiver_id);
+
+            let variables = vec![
+                ("audit".to_owned(), public_audit.project_name.clone()),
+                ("reason".to_owned(), reason.clone()),
+                ("status".to_owned(), format!("{:?}", status)),
+            ];
+
+            send_notification(&self.context, true, true, new_notification, variables).await?;
+
+            let event =
+                PublicEvent::new(receiver_id, EventPayload::AuditUpdate(public_audit.clone()));
+
+            post_event(&self.context, event, self.context.server_auth()).await?;
+        }
 
         Ok(public_audit)
     }
@@ -315,15 +658,20 @@ impl AuditService {
 
         let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
 
-        let Some(audit) = audits.delete("_id", &id).await? else {
+        let Some(audit) = audits.find("_id", &Bson::ObjectId(id)).await? else {
             return Err(anyhow::anyhow!("No audit found").code(404));
         };
 
-        if !Edit.get_access(&auth, &audit) {
-            audits.insert(&audit).await?;
+        if !Delete.get_access(&auth, &audit) {
             return Err(anyhow::anyhow!("User is not available to delete this audit").code(403));
         }
 
+        audits.delete("_id", &id).await?;
+
+        self.activity()
+            .record(id, ActivityKind::AuditDeleted, "deleted the audit")
+            .await;
+
         let public_audit = PublicAudit::new(&self.context, audit).await?;
 
         Ok(public_audit)
@@ -335,36 +683,72 @@ impl AuditService {
         issue: CreateIssue,
     ) -> error::Result<PublicIssue> {
         let auth = self.context.auth();
-        let Some(mut audit) = self.get_audit(audit_id).await? else {
+        // Reviewers and the customer only read issues
+        let audit = self.get_auditor_audit(audit_id).await?;
+
+        let cvss = issue.cvss.as_deref().map(Cvss::parse).transpose()?;
+
+        for location in &issue.locations {
+            location.validate(&audit.scope)?;
+        }
+
+        let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
+
+        let Some(id) = audits
+            .increment("_id", &audit_id, "issue_counter", 1)
+            .await?
+        else {
             return Err(anyhow::anyhow!("No audit found").code(404));
         };
 
-        let issue: Issue<ObjectId> = Issue {
-            id: rand::thread_rng().gen_range(10000..=99999) + audit.issues.len(),
+        let status = issue.status;
+        let mut issue: Issue<ObjectId> = Issue {
+            id: id as usize,
             name: issue.name,
             description: issue.description,
-            status: issue.status,
+            status: status.clone(),
             severity: issue.severity,
+            cvss,
             events: Vec::new(),
             category: issue.category,
             links: issue.links,
+            locations: issue.locations,
             include: true,
             feedback: String::new(),
+            comments: Vec::new(),
+            assignee: None,
+            due_date: None,
+            remediation_note: None,
+            overdue_notified: false,
+            status_history: vec![IssueTransition::new(status)],
             last_modified: Utc::now().timestamp(),
-            read: HashMap::new(),
         };
 
-        audit.issues.push(issue.clone());
-
-        let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
+        if auth.id().is_some() {
+            Self::create_event(
+                &self.context,
+                &mut issue,
+                EventKind::IssueCreated,
+                "created the issue".to_string(),
+            );
+        }
 
-        audits.delete("_id", &audit_id).await?;
+        self.number_events(audit_id, issue.events.iter_mut().collect())
+            .await?;
 
-        audit.issues.sort_by(|a, b| {
-            severity_to_integer(&a.severity).cmp(&severity_to_integer(&b.severity))
-        });
+        audits
+            .update(
+                "_id",
+                &audit_id,
+                doc! {"last_modified": Utc::now().timestamp_micros()},
+                doc! {"issues": to_bson(&issue)?},
+            )
+            .await?;
 
-        audits.insert(&audit).await?;
+        let message = format!("created issue {} {}", issue_code(issue.id), issue.name);
+        self.activity()
+            .record_issue(audit_id, &issue, ActivityKind::IssueCreated, message)
+            .await;
 
         if audit.no_customer {
             return Ok(auth.public_issue(issue));
@@ -388,6 +772,137 @@ impl AuditService {
         Ok(auth.public_issue(issue))
     }
 
+    pub async fn create_issue_from_template(
+        &self,
+        audit_id: ObjectId,
+        issue: CreateIssueFromTemplate,
+    ) -> error::Result<PublicIssue> {
+        let template = FindingTemplateService::new(self.context.clone())
+            .get(issue.template_id.parse()?)
+            .await?;
+
+        let substitute = |text: &str| {
+            substitute(text, &issue.variables).map_err(|name| {
+                anyhow::anyhow!("No value for template variable {}", name).code(400)
+            })
+        };
+
+        let create_issue = CreateIssue {
+            name: substitute(&template.name)?,
+            description: substitute(&template.issue_description())?,
+            status: issue.status.unwrap_or(Status::Draft),
+            severity: template.severity,
+            cvss: issue.cvss,
+            category: template.category,
+            links: issue.links,
+            locations: issue.locations,
+            feedback: None,
+        };
+
+        self.create_issue(audit_id, create_issue).await
+    }
+
+    pub async fn import_issues(
+        &self,
+        audit_id: ObjectId,
+        params: ImportParams,
+        report: serde_json::Value,
+    ) -> error::Result<ImportSummary> {
+        let auth = self.context.auth();
+        let audit = self.get_auditor_audit(audit_id).await?;
+
+        let (drafts, findings) = parse_report(&params, report, &audit.scope)?;
+
+        let existing: Vec<String> = audit
+            .issues
+            .iter()
+            .map(|issue| issue.name.trim().to_lowercase())
+            .collect();
+
+        let (skipped, drafts): (Vec<CreateIssue>, Vec<CreateIssue>) = drafts
+            .into_iter()
+            .partition(|issue| existing.contains(&issue.name.trim().to_lowercase()));
+
+        let skipped = skipped.into_iter().map(|issue| issue.name).collect();
+
+        if drafts.is_empty() {
+            return Ok(ImportSummary {
+                findings,
+                created: Vec::new(),
+                skipped,
+            });
+        }
+
+        let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
+
+        let Some(last_id) = audits
+            .increment("_id", &audit_id, "issue_counter", drafts.len() as i64)
+            .await?
+        else {
+            return Err(anyhow::anyhow!("No audit found").code(404));
+        };
+
+        let first_id = last_id as usize + 1 - drafts.len();
+
+        let mut issues = drafts
+            .into_iter()
+            .enumerate()
+            .map(|(idx, draft)| draft.to_issue(first_id + idx))
+            .collect::<error::Result<Vec<Issue<ObjectId>>>>()?;
+
+        if auth.id().is_some() {
+            for issue in &mut issues {
+                Self::create_event(
+                    &self.context,
+                    issue,
+                    EventKind::IssueCreated,
+                    "imported the issue".to_string(),
+                );
+            }
+        }
+
+        self.number_events(
+            audit_id,
+            issues
+                .iter_mut()
+                .flat_map(|issue| &mut issue.events)
+                .collect(),
+        )
+        .await?;
+
+        let bson_issues = issues
+            .iter()
+            .map(to_bson)
+            .collect::<Result<Vec<Bson>, _>>()?;
+
+        audits
+            .update(
+                "_id",
+                &audit_id,
+                doc! {"last_modified": Utc::now().timestamp_micros()},
+                doc! {"issues": {"$each": bson_issues}},
+            )
+            .await?;
+
+        // Imported issues are drafts, so only the auditor sees their import
+        if !issues.is_empty() {
+            let message = format!(
+                "imported {} issues from a {:?} report",
+                issues.len(),
+                params.format
+            );
+            self.activity()
+                .record_private(audit_id, ActivityKind::IssuesImported, message)
+                .await;
+        }
+
+        Ok(ImportSummary {
+            findings,
+            created: issues.into_iter().map(|i| auth.public_issue(i)).collect(),
+            skipped,
+        })
+    }
+
     fn create_event(
         context: &GeneralContext,
         issue: &mut Issue<ObjectId>,
@@ -399,7 +914,8 @@ impl AuditService {
             user: context.auth().id().unwrap(),
             kind,
             message,
-            id: issue.events.len(),
+            // Numbered by `number_events` before the issue is saved
+            id: 0,
         };
         issue.events.push(event);
     }
@@ -411,7 +927,7 @@ impl AuditService {
         change: ChangeIssue,
     ) -> error::Result<PublicIssue> {
         let auth = self.context.auth();
-        let Some(mut audit) = self.get_audit(audit_id).await? else {
+        let Some(audit) = self.get_audit(audit_id).await? else {
             return Err(anyhow::anyhow!("No audit found").code(404));
         };
 
@@ -419,17 +935,36 @@ impl AuditService {
             return Err(anyhow::anyhow!("User is not available to change this issue").code(403));
         }
 
+        if audit.status.is_frozen() {
+            return Err(anyhow::anyhow!("Issues of this audit are frozen").code(400));
+        }
+
         let Some(mut issue) = audit
             .issues
             .iter()
             .find(|issue| issue.id == issue_id)
             .cloned()
-            else {
-                return Err(anyhow::anyhow!("No issue found").code(404));
-            };
+        else {
+            return Err(anyhow::anyhow!("No issue found").code(404));
+        };
 
-        if let Some(name) = change.name {
-            issue.name = name;
+        let events_before = issue.events.len();
+        let mut changed = Vec::new();
+
+        let cvss = change
+            .cvss
+            .as_deref()
+            .filter(|vector| !vector.is_empty())
+            .map(Cvss::parse)
+            .transpose()?;
+
+        for location in change.locations.iter().flatten() {
+            location.validate(&audit.scope)?;
+        }
+
+        if let Some(name) = change.name {
+            issue.name = name;
+            changed.push("name");
 
             Self::create_event(
                 &self.context,
@@ -441,6 +976,7 @@ impl AuditService {
 
         if let Some(description) = change.description {
             issue.description = description;
+            changed.push("description");
 
             Self::create_event(
                 &self.context,
@@ -456,46 +992,49 @@ impl AuditService {
             Role::Auditor
         };
 
-        let receiver_id = if role == Role::Customer {
-            audit.auditor_id
+        let receivers = if role == Role::Customer {
+            audit.auditors()
         } else {
-            audit.customer_id
+            vec![audit.customer_id]
         };
 
         if let Some(action) = change.status {
             if audit.no_customer {
-                issue.status = match action {
+                let status = match action {
                     Action::Fixed => Status::Fixed,
                     Action::NotFixed => Status::NotFixed,
-                    _ => return Err(anyhow::anyhow!("Invalid action").code(400))
-                }
+                    _ => return Err(anyhow::anyhow!("Invalid action").code(400)),
+                };
+                issue.set_status(status);
+                changed.push("status");
             } else {
                 let Some(new_state) = issue.status.apply(&action) else {
                     return Err(anyhow::anyhow!("Invalid action").code(400));
                 };
 
-                let mut new_notification: NewNotification = if role == Role::Customer {
-                    serde_json::from_str(include_str!(
-                        "../../templates/audit_issue_status_change_auditor.txt"
-                    ))?
+                let template = if role == Role::Customer {
+                    include_str!("../../templates/audit_issue_status_change_auditor.txt")
                 } else {
-                    serde_json::from_str(include_str!(
-                        "../../templates/audit_issue_status_change_customer.txt"
-                    ))?
+                    include_str!("../../templates/audit_issue_status_change_customer.txt")
                 };
 
-                new_notification.user_id = Some(receiver_id);
-
                 let project = get_project(&self.context, audit.project_id).await?;
 
-                let variables = vec![
-                    ("issue".to_owned(), issue.name.clone()),
-                    ("audit".to_owned(), project.name),
-                ];
+                for receiver_id in &receivers {
+                    let mut new_notification: NewNotification = serde_json::from_str(template)?;
+                    new_notification.user_id = Some(*receiver_id);
 
-                send_notification(&self.context, true, true, new_notification, variables).await?;
+                    let variables = vec![
+                        ("issue".to_owned(), issue.name.clone()),
+                        ("audit".to_owned(), project.name.clone()),
+                    ];
+
+                    send_notification(&self.context, true, true, new_notification, variables)
+                        .await?;
+                }
 
-                issue.status = new_state.clone();
+                issue.set_status(new_state.clone());
+                changed.push("status");
 
                 Self::create_event(
                     &self.context,
@@ -506,19 +1045,33 @@ impl AuditService {
             };
         }
 
-        if let Some(severity) = change.severity.clone() {
-            issue.severity = severity.clone();
+        if let Some(severity) = change.severity {
+            issue.severity = severity;
+            changed.push("severity");
 
             Self::create_event(
                 &self.context,
                 &mut issue,
                 EventKind::IssueSeverity,
-                severity,
+                severity.to_string(),
             );
         }
 
+        if change.cvss.is_some() {
+            let message = if let Some(cvss) = &cvss {
+                format!("changed CVSS to {} ({})", cvss.vector, cvss.score)
+            } else {
+                "removed CVSS vector".to_string()
+            };
+            issue.cvss = cvss;
+            changed.push("cvss");
+
+            Self::create_event(&self.context, &mut issue, EventKind::IssueSeverity, message);
+        }
+
         if let Some(category) = change.category {
             issue.category = category.clone();
+            changed.push("category");
 
             Self::create_event(
                 &self.context,
@@ -531,6 +1084,7 @@ impl AuditService {
         if let Some(links) = change.links {
             let prev_links_length = issue.links.len();
             issue.links = links.clone();
+            changed.push("links");
 
             let message = if prev_links_length < links.len() {
                 "added new link".to_string()
@@ -541,8 +1095,37 @@ impl AuditService {
             Self::create_event(&self.context, &mut issue, EventKind::IssueLink, message);
         }
 
+        if let Some(locations) = change.locations {
+            let removed: Vec<CodeLocation> = issue
+                .locations
+                .iter()
+                .filter(|location| !locations.contains(location))
+                .cloned()
+                .collect();
+
+            let added: Vec<CodeLocation> = locations
+                .iter()
+                .filter(|location| !issue.locations.contains(location))
+                .cloned()
+                .collect();
+
+            issue.locations = locations;
+            changed.push("locations");
+
+            for location in removed {
+                let message = format!("removed location {}", location);
+                Self::create_event(&self.context, &mut issue, EventKind::IssueLocation, message);
+            }
+
+            for location in added {
+                let message = format!("added location {}", location);
+                Self::create_event(&self.context, &mut issue, EventKind::IssueLocation, message);
+            }
+        }
+
         if let Some(include) = change.include {
             issue.include = include;
+            changed.push("include");
         }
 
         if let Some(feedback) = change.feedback {
@@ -559,131 +1142,189 @@ impl AuditService {
             };
 
             issue.feedback = feedback;
+            changed.push("feedback");
 
             Self::create_event(&self.context, &mut issue, kind, message);
         }
 
-        if !audit.no_customer {
-            if let Some(events) = change.events {
-                let sender_id = auth.id().unwrap();
+        if let Some(assignee) = change.assignee {
+            let assignee = Some(assignee).filter(|assignee| !assignee.is_empty());
+            let message = match &assignee {
+                Some(assignee) => format!("assigned the issue to {}", assignee),
+                None => "removed the assignee".to_string(),
+            };
 
-                let project = get_project(&self.context, audit.project_id).await?;
+            issue.assignee = assignee;
+            changed.push("assignee");
 
-                let role = if sender_id == audit.customer_id {
-                    Role::Customer
-                } else {
-                    Role::Auditor
-                };
+            Self::create_event(
+                &self.context,
+                &mut issue,
+                EventKind::IssueRemediation,
+                message,
+            );
+        }
+
+        if let Some(due_date) = change.due_date {
+            let due_date = Some(due_date).filter(|due_date| *due_date > 0);
+            let date = due_date.and_then(|due_date| NaiveDateTime::from_timestamp_opt(due_date, 0));
+            let message = match date {
+                Some(due_date) => format!("set due date to {}", due_date.format("%Y-%m-%d")),
+                None => "removed the due date".to_string(),
+            };
 
+            issue.due_date = due_date;
+            issue.overdue_notified = false;
+            changed.extend(["due_date", "overdue_notified"]);
+
+            Self::create_event(
+                &self.context,
+                &mut issue,
+                EventKind::IssueRemediation,
+                message,
+            );
+        }
+
+        if let Some(remediation_note) = change.remediation_note {
+            issue.remediation_note = Some(remediation_note).filter(|note| !note.is_empty());
+            changed.push("remediation_note");
+
+            Self::create_event(
+                &self.context,
+                &mut issue,
+                EventKind::IssueRemediation,
+                "changed remediation note".to_string(),
+            );
+        }
+
+        let mut new_comments = Vec::new();
+
+        if !audit.no_customer {
+            if let Some(events) = change.events {
                 for create_event in events {
+                    // Comments sent as events by older clients are turned into top level comments
+                    if create_event.kind == EventKind::Comment {
+                        new_comments.push(CreateComment {
+                            text: create_event.message,
+                            parent: None,
+                        });
+                        continue;
+                    }
+
                     let event = Event {
                         timestamp: Utc::now().timestamp(),
                         user: self.context.auth().id().unwrap(),
                         kind: create_event.kind,
                         message: create_event.message,
-                        id: issue.events.len(),
+                        id: 0,
                     };
 
-                    if event.kind == EventKind::Comment {
-                        let mut new_notification: NewNotification = if role == Role::Customer {
-                            serde_json::from_str(include_str!(
-                                "../../templates/audit_issue_comment_auditor.txt"
-                            ))?
-                        } else {
-                            serde_json::from_str(include_str!(
-                                "../../templates/audit_issue_comment_customer.txt"
-                            ))?
-                        };
-
-                        new_notification.user_id = Some(receiver_id);
-
-                        let variables = vec![
-                            ("audit".to_owned(), project.name.clone()),
-                            ("issue".to_owned(), issue.name.clone()),
-                        ];
-
-                        send_notification(&self.context, true, true, new_notification, variables)
-                            .await?;
-                    }
-
                     issue.events.push(event);
                 }
-
-                issue
-                    .read
-                    .insert(sender_id.to_hex(), issue.events.len() as u64);
             }
         }
 
         issue.last_modified = Utc::now().timestamp();
 
-        // audit.issues[issue_id] = issue.clone();
-        if let Some(idx) = audit.issues.iter().position(|issue| issue.id == issue_id) {
-            audit.issues[idx] = issue.clone();
-        }
-
-        let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
+        self.number_events(audit_id, issue.events[events_before..].iter_mut().collect())
+            .await?;
+        self.save_issue(audit_id, &issue, &changed, &issue.events[events_before..])
+            .await?;
 
-        audits.delete("_id", &audit_id).await?;
+        if issue.events.len() > events_before {
+            let changes: Vec<&str> = issue.events[events_before..]
+                .iter()
+                .map(|event| event.message.as_str())
+                .collect();
 
-        if change.severity.is_some() {
-            audit.issues.sort_by(|a, b| {
-                severity_to_integer(&a.severity).cmp(&severity_to_integer(&b.severity))
-            });
+            let message = format!("{}: {}", issue_code(issue.id), changes.join(", "));
+            self.activity()
+                .record_issue(audit_id, &issue, ActivityKind::IssueChanged, message)
+                .await;
         }
 
-        audits.insert(&audit).await?;
+        for comment in new_comments {
+            self.push_comment(&audit, &mut issue, comment).await?;
+        }
 
         let public_issue = auth.public_issue(issue);
 
-        let event_reciver = if auth.id().unwrap() == audit.customer_id {
-            audit.auditor_id
-        } else {
-            audit.customer_id
-        };
-
-        let event = PublicEvent::new(
-            event_reciver,
-            EventPayload::IssueUpdate {
-                issue: public_issue.clone(),
-                audit: audit_id.to_hex(),
-            },
-        );
+        for receiver in Self::participants_except(&audit, auth.id()) {
+            let event = PublicEvent::new(
+                receiver,
+                EventPayload::IssueUpdate {
+                    issue: public_issue.clone(),
+                    audit: audit_id.to_hex(),
+                },
+            );
 
-        post_event(&self.context, event, self.context.server_auth()).await?;
+            post_event(&self.context, event, self.context.server_auth()).await?;
+        }
 
         Ok(public_issue)
     }
 
     pub async fn disclose_all(&self, audit_id: ObjectId) -> error::Result<Vec<PublicIssue>> {
         let auth = self.context.auth();
-        let audit = self.get_audit(audit_id).await?;
 
-        // TODO: make auth
+        let Some(mut audit) = self.get_audit(audit_id).await? else {
+            return Err(anyhow::anyhow!("No audit found").code(404));
+        };
 
-        if let Some(mut audit) = audit {
-            audit.issues.iter_mut().for_each(|issue| {
-                if issue.status == Status::Draft {
-                    issue.status = Status::InProgress;
-                    issue.last_modified = Utc::now().timestamp();
-                }
-            });
+        if !Disclose.get_access(&auth, &audit) {
+            return Err(anyhow::anyhow!("Only the auditor can disclose issues").code(403));
+        }
 
-            let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
-            audits.delete("_id", &audit_id).await?;
-            audits.insert(&audit).await?;
+        if audit.status.is_frozen() {
+            return Err(anyhow::anyhow!("Issues of this audit are frozen").code(400));
+        }
 
-            let issues = audit.issues;
+        let mut disclosed = 0;
+        audit.issues.iter_mut().for_each(|issue| {
+            if issue.status == Status::Draft {
+                issue.set_status(Status::InProgress);
+                issue.last_modified = Utc::now().timestamp();
+                disclosed += 1;
+            }
+        });
 
-            let issues: Vec<PublicIssue> = issues
-                .into_iter()
-                .map(|i| auth.public_issue(i))
-                .collect::<Vec<PublicIssue>>();
+        if disclosed == 0 {
+            return Ok(Self::visible_issues(&auth, audit));
+        }
 
-            return Ok(issues);
+        self.save_audit(&mut audit).await?;
+
+        let message = format!("disclosed {} issues", disclosed);
+        self.activity()
+            .record(audit.id, ActivityKind::IssuesDisclosed, message)
+            .await;
+
+        if audit.no_customer {
+            return Ok(Self::visible_issues(&auth, audit));
         }
 
-        Ok(Vec::new())
+        let mut new_notification: NewNotification =
+            serde_json::from_str(include_str!("../../templates/audit_issues_disclosed.txt"))?;
+
+        new_notification
+            .links
+            .push(format!("/audit-info/{}/customer", audit.id));
+
+        new_notification.user_id = Some(audit.customer_id);
+
+        let variables = vec![
+            ("audit".to_owned(), self.project_name(&audit).await?),
+            ("count".to_owned(), disclosed.to_string()),
+        ];
+
+        send_notification(&self.context, true, true, new_notification, variables).await?;
+
+        let public_audit = PublicAudit::new(&self.context, audit.clone()).await?;
+        let event = PublicEvent::new(audit.customer_id, EventPayload::AuditUpdate(public_audit));
+
+        post_event(&self.context, event, self.context.server_auth()).await?;
+
+        Ok(Self::visible_issues(&auth, audit))
     }
 
     pub async fn get_issues(&self, audit_id: ObjectId) -> error::Result<Vec<PublicIssue>> {
@@ -696,23 +1337,165 @@ impl AuditService {
                 return Err(anyhow::anyhow!("User is not available to read this audit").code(403));
             }
 
-            let is_customer = auth.id().unwrap() == audit.customer_id && !audit.no_customer;
+            return Ok(Self::visible_issues(&auth, audit));
+        }
 
-            let issues = audit.issues;
+        Ok(Vec::new())
+    }
 
-            let mut issues: Vec<PublicIssue> = issues
-                .into_iter()
-                .map(|i| auth.public_issue(i))
-                .collect::<Vec<PublicIssue>>();
+    fn visible_issues(auth: &Auth, audit: Audit<ObjectId>) -> Vec<PublicIssue> {
+        let is_customer = auth.id() == Some(audit.customer_id) && !audit.no_customer;
+
+        let mut issues: Vec<PublicIssue> = audit
+            .issues
+            .into_iter()
+            .map(|i| auth.public_issue(i))
+            .collect::<Vec<PublicIssue>>();
+
+        issues.sort_by_key(|issue| issue.severity);
 
-            if is_customer {
-                issues.retain(|issue| issue.status != Status::Draft);
+        if is_customer {
+            issues.retain(|issue| issue.status != Status::Draft);
+        }
+
+        issues
+    }
+
+    /// Notifies both sides once about every open issue whose due date has passed.
+    pub async fn notify_overdue(&self, audit: &Audit<ObjectId>) -> error::Result<usize> {
+        if audit.no_customer {
+            return Ok(0);
+        }
+
+        let now = Utc::now().timestamp();
+        let overdue: Vec<&Issue<ObjectId>> = audit
+            .issues
+            .iter()
+            .filter(|issue| {
+                issue.status != Status::Draft && issue.is_overdue(now) && !issue.overdue_notified
+            })
+            .collect();
+
+        if overdue.is_empty() {
+            return Ok(0);
+        }
+
+        let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
+        let project_name = self.project_name(audit).await?;
+
+        for issue in &overdue {
+            let issue_id = Bson::Int64(issue.id as i64);
+            audits
+                .update_element(
+                    "_id",
+                    &audit.id,
+                    "issues",
+                    &issue_id,
+                    doc! {"issues.$.overdue_notified": true},
+                    doc! {},
+                )
+                .await?;
+
+            let due_date = issue
+                .due_date
+                .and_then(|due_date| NaiveDateTime::from_timestamp_opt(due_date, 0))
+                .map(|due_date| due_date.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+
+            let mut receivers = vec![(
+                audit.customer_id,
+                include_str!("../../templates/audit_issue_overdue_customer.txt"),
+            )];
+            receivers.extend(audit.auditors().into_iter().map(|auditor_id| {
+                (
+                    auditor_id,
+                    include_str!("../../templates/audit_issue_overdue_auditor.txt"),
+                )
+            }));
+
+            for (receiver_id, template) in receivers {
+                let mut new_notification: NewNotification = serde_json::from_str(template)?;
+                new_notification.user_id = Some(receiver_id);
+
+                let variables = vec![
+                    ("audit".to_owned(), project_name.clone()),
+                    ("issue".to_owned(), issue.name.clone()),
+                    ("due_date".to_owned(), due_date.clone()),
+                ];
+
+                send_notification(&self.context, true, true, new_notification, variables).await?;
             }
+        }
+
+        Ok(overdue.len())
+    }
+
+    pub async fn remediation(&self, audit_id: ObjectId) -> error::Result<RemediationSummary> {
+        let auth = self.context.auth();
+
+        let Some(audit) = self.get_audit(audit_id).await? else {
+            return Err(anyhow::anyhow!("No audit found").code(404));
+        };
+
+        let mut issues = Self::visible_issues(&auth, audit);
+        issues.retain(|issue| issue.include);
+        issues.sort_by_key(|issue| (issue.due_date.is_none(), issue.due_date, issue.severity));
+
+        let items: Vec<RemediationItem> = issues
+            .into_iter()
+            .map(|issue| RemediationItem {
+                issue_id: issue.id,
+                code: issue.code,
+                name: issue.name,
+                severity: issue.severity,
+                status: issue.status,
+                assignee: issue.assignee,
+                due_date: issue.due_date,
+                remediation_note: issue.remediation_note,
+                overdue: issue.overdue,
+            })
+            .collect();
+
+        let open = items
+            .iter()
+            .filter(|item| item.status != Status::Fixed && item.status != Status::NotFixed);
+
+        Ok(RemediationSummary {
+            total: items.len(),
+            resolved: items.len() - open.clone().count(),
+            overdue: items.iter().filter(|item| item.overdue).count(),
+            unassigned: open.clone().filter(|item| item.assignee.is_none()).count(),
+            without_due_date: open.filter(|item| item.due_date.is_none()).count(),
+            issues: items,
+        })
+    }
 
-            return Ok(issues);
+    pub async fn export_issues(
+        &self,
+        audit_id: ObjectId,
+        format: ExportFormat,
+    ) -> error::Result<String> {
+        let auth = self.context.auth();
+
+        let Some(audit) = self.get_audit(audit_id).await? else {
+            return Err(anyhow::anyhow!("No audit found").code(404));
+        };
+
+        if !Read.get_access(&auth, &audit) {
+            return Err(anyhow::anyhow!("User is not available to read this audit").code(403));
         }
 
-        Ok(Vec::new())
+        let project_name = self.project_name(&audit).await?;
+
+        let issues = Self::visible_issues(&auth, audit);
+
+        let result = match format {
+            ExportFormat::Sarif => serde_json::to_string_pretty(&export::to_sarif(&issues))?,
+            ExportFormat::Csv => export::to_csv(&issues),
+            ExportFormat::Markdown => export::to_markdown(&project_name, &issues),
+        };
+
+        Ok(result)
     }
 
     pub async fn get_issue_by_id(
@@ -724,14 +1507,12 @@ impl AuditService {
         let audit = self.get_audit(audit_id).await?;
 
         if let Some(audit) = audit {
-            let issue = audit
-                .issues
-                .iter()
-                .find(|issue| issue.id == issue_id)
-                .cloned();
+            let issue = Self::visible_issues(&auth, audit)
+                .into_iter()
+                .find(|issue| issue.id == issue_id);
 
             if let Some(issue) = issue {
-                return Ok(auth.public_issue(issue));
+                return Ok(issue);
             }
         }
 
@@ -752,62 +1533,593 @@ impl AuditService {
             return Err(anyhow::anyhow!("User is not available to delete this issue").code(403));
         }
 
+        if audit.status.is_frozen() {
+            return Err(anyhow::anyhow!("Issues of this audit are frozen").code(400));
+        }
+
         let Some(issue) = audit
             .issues
             .iter()
             .find(|issue| issue.id == issue_id)
             .cloned()
-            else {
-                return Err(anyhow::anyhow!("No issue found").code(404));
-            };
+        else {
+            return Err(anyhow::anyhow!("No issue found").code(404));
+        };
 
         audit.issues.retain(|issue| issue.id != issue_id);
 
-        let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
-        audits.delete("_id", &audit_id).await?;
-        audits.insert(&audit).await?;
+        self.save_audit(&mut audit).await?;
+
+        let message = format!("deleted issue {} {}", issue_code(issue.id), issue.name);
+        self.activity()
+            .record_issue(audit_id, &issue, ActivityKind::IssueDeleted, message)
+            .await;
         let public_issue = auth.public_issue(issue);
 
         Ok(public_issue)
     }
 
-    pub async fn read_events(
+    /// The customer and the auditor team, without `user`.
+    fn participants_except(audit: &Audit<ObjectId>, user: Option<ObjectId>) -> Vec<ObjectId> {
+        let mut participants = audit.auditors();
+        if !audit.no_customer {
+            participants.push(audit.customer_id);
+        }
+        participants.retain(|participant| Some(*participant) != user);
+        participants
+    }
+
+    async fn get_auditor_audit(&self, audit_id: ObjectId) -> error::Result<Audit<ObjectId>> {
+        let auth = self.context.auth();
+        let Some(audit) = self.get_audit(audit_id).await? else {
+            return Err(anyhow::anyhow!("No audit found").code(404));
+        };
+
+        let is_auditor = match auth {
+            Auth::User(id) => audit.is_editor(id),
+            _ => Edit.get_access(&auth, &audit),
+        };
+
+        if !is_auditor {
+            return Err(anyhow::anyhow!("Only the auditor can change this audit").code(403));
+        }
+
+        if audit.status.is_frozen() {
+            return Err(anyhow::anyhow!("Issues of this audit are frozen").code(400));
+        }
+
+        Ok(audit)
+    }
+
+    async fn post_review_update(&self, audit: Audit<ObjectId>) -> error::Result<()> {
+        if audit.no_customer {
+            return Ok(());
+        }
+
+        let customer_id = audit.customer_id;
+        let public_audit = PublicAudit::new(&self.context, audit).await?;
+        let event = PublicEvent::new(customer_id, EventPayload::AuditUpdate(public_audit));
+
+        post_event(&self.context, event, self.context.server_auth()).await
+    }
+
+    pub async fn create_review_round(
         &self,
         audit_id: ObjectId,
-        issue_id: usize,
-        read: u64,
+        round: CreateReviewRound,
+    ) -> error::Result<ReviewRound> {
+        let mut audit = self.get_auditor_audit(audit_id).await?;
+
+        let round = ReviewRound {
+            id: audit.review_rounds.len() + 1,
+            commit: round.commit,
+            scope: round.scope.unwrap_or_else(|| audit.scope.clone()),
+            time: round.time,
+            verdicts: Vec::new(),
+            created: Utc::now().timestamp(),
+        };
+
+        audit.review_rounds.push(round.clone());
+
+        self.save_audit(&mut audit).await?;
+
+        let message = format!("started review round {} on {}", round.id, round.commit);
+        self.activity()
+            .record(audit.id, ActivityKind::ReviewRoundCreated, message)
+            .await;
+        self.post_review_update(audit).await?;
+
+        Ok(round)
+    }
+
+    pub async fn change_review_round(
+        &self,
+        audit_id: ObjectId,
+        round_id: usize,
+        change: ChangeReviewRound,
+    ) -> error::Result<ReviewRound> {
+        let mut audit = self.get_auditor_audit(audit_id).await?;
+
+        let issue_ids: Vec<usize> = audit.issues.iter().map(|issue| issue.id).collect();
+
+        let Some(round) = audit
+            .review_rounds
+            .iter_mut()
+            .find(|round| round.id == round_id)
+        else {
+            return Err(anyhow::anyhow!("No review round found").code(404));
+        };
+
+        if let Some(commit) = change.commit {
+            round.commit = commit;
+        }
+
+        if let Some(scope) = change.scope {
+            round.scope = scope;
+        }
+
+        if let Some(time) = change.time {
+            round.time = time;
+        }
+
+        for verdict in change.verdicts.unwrap_or_default() {
+            if !issue_ids.contains(&verdict.issue_id) {
+                return Err(
+                    anyhow::anyhow!("No issue {} found", issue_code(verdict.issue_id)).code(400),
+                );
+            }
+
+            round.set_verdict(verdict);
+        }
+
+        let round = round.clone();
+
+        self.save_audit(&mut audit).await?;
+
+        let message = format!("changed review round {}", round.id);
+        self.activity()
+            .record(audit.id, ActivityKind::ReviewRoundChanged, message)
+            .await;
+        self.post_review_update(audit).await?;
+
+        Ok(round)
+    }
+
+    pub async fn project_name(&self, audit: &Audit<ObjectId>) -> error::Result<String> {
+        if audit.project_name.is_empty() && !audit.no_customer {
+            Ok(get_project(&self.context, audit.project_id).await?.name)
+        } else {
+            Ok(audit.project_name.clone())
+        }
+    }
+
+    async fn resolve_mentions(
+        &self,
+        audit: &Audit<ObjectId>,
+        text: &str,
+    ) -> error::Result<Vec<ObjectId>> {
+        let tokens: Vec<String> = text
+            .split('@')
+            .skip(1)
+            .map(|rest| {
+                rest.chars()
+                    .take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+                    .collect::<String>()
+                    .trim_end_matches('.')
+                    .to_lowercase()
+            })
+            .filter(|token| !token.is_empty())
+            .collect();
+
+        if tokens.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let participants = Self::participants_except(audit, None);
+        let users = request_users(&self.context, participants, self.context.server_auth()).await?;
+
+        let mut mentions = Vec::new();
+        for user in users {
+            let name: String = user
+                .name
+                .split_whitespace()
+                .collect::<String>()
+                .to_lowercase();
+
+            if tokens
+                .iter()
+                .any(|token| token == &user.id || token == &name)
+            {
+                mentions.push(user.id.parse()?);
+            }
+        }
+
+        Ok(mentions)
+    }
+
+    async fn notify_comment(
+        &self,
+        audit: &Audit<ObjectId>,
+        issue: &Issue<ObjectId>,
+        author: ObjectId,
+        mentions: &[ObjectId],
+        notify_participants: bool,
     ) -> error::Result<()> {
+        if audit.no_customer {
+            return Ok(());
+        }
+
+        let project_name = self.project_name(audit).await?;
+
+        let mut receivers: Vec<_> = audit
+            .auditors()
+            .into_iter()
+            .map(|auditor_id| (auditor_id, Role::Auditor))
+            .collect();
+        receivers.push((audit.customer_id, Role::Customer));
+
+        for (receiver_id, role) in receivers {
+            if receiver_id == author {
+                continue;
+            }
+
+            let template = match (mentions.contains(&receiver_id), role) {
+                (true, Role::Auditor) => {
+                    include_str!("../../templates/audit_issue_mention_auditor.txt")
+                }
+                (true, Role::Customer) => {
+                    include_str!("../../templates/audit_issue_mention_customer.txt")
+                }
+                (false, _) if !notify_participants => continue,
+                (false, Role::Auditor) => {
+                    include_str!("../../templates/audit_issue_comment_auditor.txt")
+                }
+                (false, Role::Customer) => {
+                    include_str!("../../templates/audit_issue_comment_customer.txt")
+                }
+            };
+
+            let mut new_notification: NewNotification = serde_json::from_str(template)?;
+            new_notification.user_id = Some(receiver_id);
+
+            let variables = vec![
+                ("audit".to_owned(), project_name.clone()),
+                ("issue".to_owned(), issue.name.clone()),
+            ];
+
+            send_notification(&self.context, true, true, new_notification, variables).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn push_comment(
+        &self,
+        audit: &Audit<ObjectId>,
+        issue: &mut Issue<ObjectId>,
+        comment: CreateComment,
+    ) -> error::Result<Comment<ObjectId>> {
         let auth = self.context.auth();
+        let Some(author) = auth.id() else {
+            return Err(anyhow::anyhow!("Only users can comment").code(403));
+        };
 
-        let audit = self.get_audit(audit_id).await?;
+        if comment.text.trim().is_empty() {
+            return Err(anyhow::anyhow!("Comment is empty").code(400));
+        }
 
-        if let Some(mut audit) = audit {
-            let issue = audit
-                .issues
-                .iter_mut()
-                .find(|issue| issue.id == issue_id);
+        let parent = comment.parent.map(|parent| parent.parse()).transpose()?;
 
-            if let Some(issue) = issue {
-                issue.read.insert(auth.id().unwrap().to_hex(), read);
+        if let Some(parent) = parent {
+            let parent_exists = issue
+                .comments
+                .iter()
+                .any(|comment| comment.id == parent && !comment.deleted);
+
+            if !parent_exists {
+                return Err(anyhow::anyhow!("No parent comment found").code(400));
             }
+        }
 
-            let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
+        let mentions = self.resolve_mentions(audit, &comment.text).await?;
 
-            audits.delete("_id", &audit_id).await?;
+        let now = Utc::now().timestamp();
+        let comment = Comment {
+            id: ObjectId::new(),
+            parent,
+            user: author,
+            text: comment.text,
+            timestamp: now,
+            edits: Vec::new(),
+            deleted: false,
+            mentions,
+            read: HashMap::from([(author.to_hex(), now)]),
+        };
+
+        let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
+
+        let issue_id = Bson::Int64(issue.id as i64);
+        if !audits
+            .update_element(
+                "_id",
+                &audit.id,
+                "issues",
+                &issue_id,
+                doc! {"last_modified": Utc::now().timestamp_micros()},
+                doc! {"issues.$.comments": to_bson(&comment)?},
+            )
+            .await?
+        {
+            return Err(anyhow::anyhow!("No issue found").code(404));
+        }
+
+        issue.comments.push(comment.clone());
+
+        let message = format!("commented on {}", issue_code(issue.id));
+        self.activity()
+            .record_issue(audit.id, issue, ActivityKind::CommentAdded, message)
+            .await;
+
+        self.notify_comment(audit, issue, author, &comment.mentions, true)
+            .await?;
+
+        Ok(comment)
+    }
+
+    async fn get_comment_issue(
+        &self,
+        audit_id: ObjectId,
+        issue_id: usize,
+    ) -> error::Result<(Audit<ObjectId>, Issue<ObjectId>)> {
+        let auth = self.context.auth();
+        let Some(audit) = self.get_audit(audit_id).await? else {
+            return Err(anyhow::anyhow!("No audit found").code(404));
+        };
 
-            audits.insert(&audit).await?;
+        if !Edit.get_access(&auth, &audit) {
+            return Err(anyhow::anyhow!("User is not available to comment this issue").code(403));
+        }
+
+        let is_customer = auth.id() == Some(audit.customer_id) && !audit.no_customer;
 
+        let Some(issue) = audit
+            .issues
+            .iter()
+            .find(|issue| issue.id == issue_id && !(is_customer && issue.status == Status::Draft))
+            .cloned()
+        else {
+            return Err(anyhow::anyhow!("No issue found").code(404));
+        };
+
+        Ok((audit, issue))
+    }
+
+    async fn post_issue_update(
+        &self,
+        audit: &Audit<ObjectId>,
+        issue: Issue<ObjectId>,
+    ) -> error::Result<()> {
+        let auth = self.context.auth();
+        let public_issue = auth.public_issue(issue);
+
+        for receiver in Self::participants_except(audit, auth.id()) {
+            let event = PublicEvent::new(
+                receiver,
+                EventPayload::IssueUpdate {
+                    issue: public_issue.clone(),
+                    audit: audit.id.to_hex(),
+                },
+            );
+
+            post_event(&self.context, event, self.context.server_auth()).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn create_comment(
+        &self,
+        audit_id: ObjectId,
+        issue_id: usize,
+        comment: CreateComment,
+    ) -> error::Result<PublicComment> {
+        let (audit, mut issue) = self.get_comment_issue(audit_id, issue_id).await?;
+
+        let comment = self.push_comment(&audit, &mut issue, comment).await?;
+
+        self.post_issue_update(&audit, issue).await?;
+
+        Ok(PublicComment::new(comment, self.context.auth().id()))
+    }
+
+    fn find_comment(
+        issue: &Issue<ObjectId>,
+        comment_id: ObjectId,
+    ) -> error::Result<(usize, Comment<ObjectId>)> {
+        issue
+            .comments
+            .iter()
+            .enumerate()
+            .find(|(_, comment)| comment.id == comment_id)
+            .map(|(idx, comment)| (idx, comment.clone()))
+            .ok_or_else(|| anyhow::anyhow!("No comment found").code(404))
+    }
+
+    fn check_comment_author(&self, comment: &Comment<ObjectId>) -> error::Result<()> {
+        match self.context.auth() {
+            Auth::Admin(_) | Auth::Service(_, _) => Ok(()),
+            Auth::User(id) if id == comment.user => Ok(()),
+            _ => Err(anyhow::anyhow!("Only the author can change this comment").code(403)),
+        }
+    }
+
+    /// Updates the comments matching `comments`, `$[nested]` in the paths refers to them.
+    async fn update_comment(
+        &self,
+        audit: &Audit<ObjectId>,
+        issue: &Issue<ObjectId>,
+        comments: Document,
+        set: Document,
+        push: Document,
+    ) -> error::Result<()> {
+        let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
+
+        let mut set = set;
+        set.insert("last_modified", Utc::now().timestamp_micros());
+
+        let issue_id = Bson::Int64(issue.id as i64);
+        if !audits
+            .update_nested_element("_id", &audit.id, "issues", &issue_id, comments, set, push)
+            .await?
+        {
+            return Err(anyhow::anyhow!("No issue found").code(404));
+        }
+
+        Ok(())
+    }
+
+    pub async fn change_comment(
+        &self,
+        audit_id: ObjectId,
+        issue_id: usize,
+        comment_id: ObjectId,
+        change: ChangeComment,
+    ) -> error::Result<PublicComment> {
+        let (audit, mut issue) = self.get_comment_issue(audit_id, issue_id).await?;
+        let (idx, mut comment) = Self::find_comment(&issue, comment_id)?;
+
+        self.check_comment_author(&comment)?;
+
+        if comment.deleted {
+            return Err(anyhow::anyhow!("Comment is deleted").code(400));
+        }
+
+        if change.text.trim().is_empty() {
+            return Err(anyhow::anyhow!("Comment is empty").code(400));
+        }
+
+        let edit = CommentEdit {
+            text: std::mem::replace(&mut comment.text, change.text),
+            timestamp: Utc::now().timestamp(),
+        };
+
+        let mentions = self.resolve_mentions(&audit, &comment.text).await?;
+        let new_mentions: Vec<ObjectId> = mentions
+            .iter()
+            .filter(|id| !comment.mentions.contains(id))
+            .cloned()
+            .collect();
+
+        comment.edits.push(edit.clone());
+        comment.mentions = mentions;
+
+        self.update_comment(
+            &audit,
+            &issue,
+            doc! {"nested.id": comment.id},
+            doc! {
+                "issues.$.comments.$[nested].text": &comment.text,
+                "issues.$.comments.$[nested].mentions": to_bson(&comment.mentions)?,
+            },
+            doc! {"issues.$.comments.$[nested].edits": to_bson(&edit)?},
+        )
+        .await?;
+
+        issue.comments[idx] = comment.clone();
+
+        let message = format!("edited a comment on {}", issue_code(issue.id));
+        self.activity()
+            .record_issue(audit.id, &issue, ActivityKind::CommentChanged, message)
+            .await;
+
+        self.notify_comment(&audit, &issue, comment.user, &new_mentions, false)
+            .await?;
+        self.post_issue_update(&audit, issue).await?;
+
+        Ok(PublicComment::new(comment, self.context.auth().id()))
+    }
+
+    pub async fn delete_comment(
+        &self,
+        audit_id: ObjectId,
+        issue_id: usize,
+        comment_id: ObjectId,
+    ) -> error::Result<PublicComment> {
+        let (audit, mut issue) = self.get_comment_issue(audit_id, issue_id).await?;
+        let (idx, mut comment) = Self::find_comment(&issue, comment_id)?;
+
+        self.check_comment_author(&comment)?;
+
+        comment.deleted = true;
+
+        self.update_comment(
+            &audit,
+            &issue,
+            doc! {"nested.id": comment.id},
+            doc! {"issues.$.comments.$[nested].deleted": true},
+            doc! {},
+        )
+        .await?;
+
+        issue.comments[idx] = comment.clone();
+
+        let message = format!("deleted a comment on {}", issue_code(issue.id));
+        self.activity()
+            .record_issue(audit.id, &issue, ActivityKind::CommentDeleted, message)
+            .await;
+
+        self.post_issue_update(&audit, issue).await?;
+
+        Ok(PublicComment::new(comment, self.context.auth().id()))
+    }
+
+    /// Marks the comment as read, or every comment of the issue when `comment_id` is `None`.
+    pub async fn read_comments(
+        &self,
+        audit_id: ObjectId,
+        issue_id: usize,
+        comment_id: Option<ObjectId>,
+    ) -> error::Result<()> {
+        let Some(user) = self.context.auth().id() else {
+            return Err(anyhow::anyhow!("Only users can read comments").code(403));
+        };
+
+        let (audit, issue) = self.get_comment_issue(audit_id, issue_id).await?;
+
+        let comments = match comment_id {
+            Some(comment_id) => vec![Self::find_comment(&issue, comment_id)?.1],
+            None => issue.comments.clone(),
+        };
+
+        let unread: Vec<ObjectId> = comments
+            .iter()
+            .filter(|comment| !comment.is_read_by(&user))
+            .map(|comment| comment.id)
+            .collect();
+
+        if unread.is_empty() {
             return Ok(());
         }
 
-        Err(anyhow::anyhow!("No issue found").code(404))
+        let read = format!("issues.$.comments.$[nested].read.{}", user.to_hex());
+        self.update_comment(
+            &audit,
+            &issue,
+            doc! {"nested.id": {"$in": unread}},
+            doc! {read: Utc::now().timestamp()},
+            doc! {},
+        )
+        .await
     }
 
     pub async fn find_public(
         &self,
         user: ObjectId,
         role: String,
+        pagination: PaginationParams,
     ) -> error::Result<Vec<PublicAudit>> {
+        let (skip, limit) = pagination.skip_limit(MAX_PER_PAGE)?;
+
         let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
 
         let role = role.to_ascii_lowercase() + "_id";
@@ -816,14 +2128,44 @@ impl AuditService {
             return Err(anyhow::anyhow!("Invalid role").code(400));
         }
 
-        let audits = audits.find_many(&role, &Bson::ObjectId(user)).await?;
+        let (audits, _) = audits
+            .find_page(
+                doc! {role: user, "public": true},
+                doc! {"last_modified": -1},
+                skip,
+                limit,
+            )
+            .await?;
+
+        PublicAudit::new_many(&self.context, audits).await
+    }
+
+    pub async fn verify_report(
+        &self,
+        id: ObjectId,
+        report: &[u8],
+    ) -> error::Result<ReportVerification> {
+        let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
 
-        let mut result = vec![];
+        let Some(audit) = audits.find("_id", &Bson::ObjectId(id)).await? else {
+            return Err(anyhow::anyhow!("No audit found").code(404));
+        };
 
-        for audit in audits {
-            result.push(PublicAudit::new(&self.context, audit).await?);
+        if !audit.public || audit.status != AuditStatus::Resolved {
+            return Err(anyhow::anyhow!("No audit found").code(404));
         }
 
-        Ok(result)
+        let Some(report_sha256) = audit.report_sha256.clone() else {
+            return Err(anyhow::anyhow!("Report of this audit is not hashed").code(404));
+        };
+
+        let project_name = self.project_name(&audit).await?;
+
+        Ok(ReportVerification {
+            audit_id: audit.id.to_hex(),
+            project_name,
+            matches: report_sha256.eq_ignore_ascii_case(&report_digest(report)),
+            report_sha256,
+        })
     }
 }
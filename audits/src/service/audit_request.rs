@@ -5,26 +5,36 @@ use common::{
         auditor::request_auditor,
         badge::BadgePayload,
         codes::post_code,
+        events::post_event,
         events::{EventPayload, PublicEvent},
         mail::send_mail,
-        requests::CreateRequest,
-        seartch::PaginationParams,
+        organization::request_organization,
+        requests::{
+            AssignRequest, CreateRequest, MyAuditRequestResult, OfferAction, RequestFilter,
+            RequestOffer,
+        },
+        seartch::{PaginationParams, MAX_PER_PAGE},
         send_notification, NewNotification,
     },
     context::GeneralContext,
     entities::{
-        audit_request::{AuditRequest, PriceRange, TimeRange},
+        activity::ActivityKind,
+        audit::TeamMember,
+        audit_request::{AuditRequest, Offer, OfferStatus, PriceRange, TimeRange},
         auditor::ExtendedAuditor,
         letter::CreateLetter,
+        organization::OrganizationRole,
         project::get_project,
         role::Role,
     },
-    error::{self, AddCode},
+    error::{self, AddCode, Validation},
     services::{API_PREFIX, CUSTOMERS_SERVICE, EVENTS_SERVICE, FRONTEND, PROTOCOL},
 };
 
 pub use common::api::requests::PublicRequest;
-use mongodb::bson::{oid::ObjectId, Bson};
+
+use super::activity::ActivityService;
+use mongodb::bson::{doc, oid::ObjectId, Bson};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -34,15 +44,72 @@ pub struct RequestChange {
     project_scope: Option<Vec<String>>,
     price_range: Option<PriceRange>,
     price: Option<i64>,
+    expires_at: Option<i64>,
+    team: Option<Vec<TeamMember<String>>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct MyAuditRequestResult {
-    pub result: Vec<PublicRequest>,
-    #[serde(rename = "totalDocuments")]
-    pub total_documents: u64,
+/// Checks the terms that are given, `time` and `expires_at` are in milliseconds.
+fn validate_terms(
+    price: Option<i64>,
+    time: Option<&TimeRange>,
+    price_range: Option<&PriceRange>,
+    scope: Option<&[String]>,
+    expires_at: Option<i64>,
+) -> error::Result<()> {
+    let now = Utc::now();
+    let mut validation = Validation::default();
+
+    if let Some(price) = price {
+        validation.check(price >= 0, "price", "Price must not be negative");
+    }
+
+    if let Some(time) = time {
+        validation.check(
+            time.from <= time.to,
+            "time",
+            "Start of the audit must not be after its end",
+        );
+        validation.check(
+            time.to >= now.timestamp_millis(),
+            "time",
+            "Audit must not end in the past",
+        );
+    }
+
+    if let Some(price_range) = price_range {
+        validation.check(
+            price_range.from >= 0,
+            "price_range",
+            "Price range must not be negative",
+        );
+        validation.check(
+            price_range.from <= price_range.to,
+            "price_range",
+            "Start of the price range must not be after its end",
+        );
+    }
+
+    if let Some(scope) = scope {
+        validation.check(
+            scope.iter().all(|link| !link.trim().is_empty()),
+            "project_scope",
+            "Scope must not contain empty links",
+        );
+    }
+
+    if let Some(expires_at) = expires_at {
+        validation.check(
+            expires_at > now.timestamp_millis(),
+            "expires_at",
+            "Request expiry must be in the future",
+        );
+    }
+
+    validation.finish()
 }
 
+const PAGE_SIZE: i32 = 100;
+
 pub struct RequestService {
     context: GeneralContext,
 }
@@ -67,7 +134,36 @@ impl RequestService {
         };
 
         let customer_id = request.customer_id.parse()?;
-        let auditor_id = request.auditor_id.parse()?;
+
+        let organization_id = match &request.organization_id {
+            Some(organization_id) => Some(organization_id.parse::<ObjectId>()?),
+            None => None,
+        };
+
+        let auditor_id = if let Some(organization_id) = organization_id {
+            let organization =
+                request_organization(&self.context, organization_id, self.context.server_auth())
+                    .await?;
+
+            let member = organization
+                .members
+                .iter()
+                .find(|member| member.user_id == request.auditor_id)
+                .or_else(|| {
+                    organization
+                        .members
+                        .iter()
+                        .find(|member| member.role == OrganizationRole::Admin)
+                });
+
+            let Some(member) = member else {
+                return Err(anyhow::anyhow!("Organization has no auditor to assign").code(400));
+            };
+
+            member.user_id.parse()?
+        } else {
+            request.auditor_id.parse()?
+        };
 
         if customer_id == auditor_id {
             return Err(anyhow::anyhow!("You can't create audit with yourself").code(400));
@@ -83,6 +179,14 @@ impl RequestService {
             );
         };
 
+        validate_terms(
+            Some(request.price),
+            Some(&request.time),
+            None,
+            None,
+            request.expires_at,
+        )?;
+
         let mut request = AuditRequest {
             id: ObjectId::new(),
             customer_id,
@@ -93,23 +197,58 @@ impl RequestService {
             price: request.price,
             last_modified: Utc::now().timestamp_micros(),
             last_changer,
+            offers: Vec::new(),
+            expires_at: request.expires_at,
+            price_range: None,
+            team: Vec::new(),
+            organization_id,
+        };
+
+        let offer = Offer {
+            author: user_id,
+            role: last_changer,
+            price: request.price,
+            time: request.time.clone(),
+            scope: Vec::new(),
+            message: String::new(),
+            status: OfferStatus::Pending,
+            timestamp: Utc::now().timestamp(),
         };
 
         let old_version_of_this_request = requests
             .find_many("project_id", &Bson::ObjectId(request.project_id))
             .await?
             .into_iter()
-            .filter(|r| r.customer_id == request.customer_id && r.auditor_id == request.auditor_id)
+            .filter(|r| {
+                r.customer_id == request.customer_id
+                    && (r.auditor_id == request.auditor_id
+                        || (request.organization_id.is_some()
+                            && r.organization_id == request.organization_id))
+            })
             .collect::<Vec<_>>()
             .pop();
 
         let project = get_project(&self.context, request.project_id).await?;
 
+        let (activity_kind, activity_message) = match &old_version_of_this_request {
+            Some(old) => (
+                ActivityKind::RequestChanged,
+                format!("sent a new offer, price {} -> {}", old.price, request.price),
+            ),
+            None => (
+                ActivityKind::RequestCreated,
+                format!("sent a request with price {}", request.price),
+            ),
+        };
+
         if let Some(old_version_of_this_request) = old_version_of_this_request {
             requests
                 .delete("id", &old_version_of_this_request.id)
                 .await?;
             request.id = old_version_of_this_request.id;
+            request.auditor_id = old_version_of_this_request.auditor_id;
+            request.offers = old_version_of_this_request.offers;
+            request.team = old_version_of_this_request.team;
         } else if last_changer == Role::Customer {
             let mut new_notification: NewNotification =
                 serde_json::from_str(include_str!("../../templates/new_audit_request.txt"))?;
@@ -205,8 +344,14 @@ impl RequestService {
             send_mail(&self.context, letter).await?;
         }
 
+        request.push_offer(offer);
+
         requests.insert(&request).await?;
 
+        ActivityService::new(self.context.clone())
+            .record(request.id, activity_kind, activity_message)
+            .await;
+
         let event_reciver = if auth.id().unwrap() == customer_id {
             auditor_id
         } else {
@@ -260,11 +405,9 @@ impl RequestService {
         &self,
         role: Role,
         pagination: PaginationParams,
-    ) -> error::Result<Vec<PublicRequest>> {
-        let page = pagination.page.unwrap_or(0);
-        let per_page = pagination.per_page.unwrap_or(0);
-        let limit = pagination.per_page.unwrap_or(1000);
-        let skip = (page - 1) * per_page;
+        filter: RequestFilter,
+    ) -> error::Result<MyAuditRequestResult> {
+        let (skip, limit) = pagination.skip_limit(MAX_PER_PAGE)?;
 
         let auth = self.context.auth();
 
@@ -278,28 +421,19 @@ impl RequestService {
             );
         };
 
-        let id = match role {
-            Role::Auditor => "auditor_id",
-            Role::Customer => "customer_id",
-        };
-
-        let (result, _total_documents) = requests
-            .find_many_limit(id, &Bson::ObjectId(user_id), skip, limit)
+        let (result, total_documents) = requests
+            .find_page(
+                filter.to_document(role, user_id)?,
+                filter.sort(),
+                skip,
+                limit,
+            )
             .await?;
 
-        let mut public_requests = Vec::new();
-
-        for req in result {
-            let public_request = PublicRequest::new(&self.context, req).await?;
-
-            public_requests.push(public_request);
-        }
-
-        // Ok(MyAuditRequestResult {
-        //     result: public_requests,
-        //     total_documents,
-        // })
-        Ok(public_requests)
+        Ok(MyAuditRequestResult {
+            result: PublicRequest::new_many(&self.context, result).await?,
+            total_documents,
+        })
     }
 
     pub async fn change(
@@ -321,35 +455,302 @@ impl RequestService {
             return Err(anyhow::anyhow!("User is not available to change this customer").code(400));
         }
 
+        let role = if auth.id() == Some(request.customer_id) {
+            Role::Customer
+        } else if auth.id() == Some(request.auditor_id) {
+            Role::Auditor
+        } else {
+            return Err(anyhow::anyhow!("User is not available to change this customer").code(400));
+        };
+
+        validate_terms(
+            change.price,
+            change.time.as_ref(),
+            change.price_range.as_ref(),
+            change.project_scope.as_deref(),
+            change.expires_at,
+        )?;
+
+        let mut changed = Vec::new();
+
         if let Some(description) = change.description {
             request.description = description;
+            changed.push("description".to_string());
         }
 
-        if let Some(time) = change.time {
-            request.time = time;
+        if let Some(expires_at) = change.expires_at {
+            request.expires_at = Some(expires_at);
+            changed.push("expiry".to_string());
         }
 
-        if let Some(price) = change.price {
-            request.price = price;
+        if let Some(price_range) = change.price_range {
+            request.price_range = Some(price_range);
+            changed.push("price range".to_string());
         }
 
-        let role = if auth.id() == Some(request.customer_id) {
-            Role::Customer
-        } else if auth.id() == Some(request.auditor_id) {
-            Role::Auditor
-        } else {
-            return Err(anyhow::anyhow!("User is not available to change this customer").code(400));
+        if let Some(team) = change.team {
+            if role != Role::Auditor {
+                return Err(anyhow::anyhow!("Only the lead auditor can change the team").code(403));
+            }
+
+            request.team = TeamMember::parse_team(team, request.auditor_id, request.customer_id)?;
+            changed.push("team".to_string());
+        }
+
+        if change.price.is_some() || change.time.is_some() || change.project_scope.is_some() {
+            if let Some(price) = change.price {
+                changed.push(format!("price {} -> {}", request.price, price));
+            }
+            if change.time.is_some() {
+                changed.push("time".to_string());
+            }
+            if change.project_scope.is_some() {
+                changed.push("scope".to_string());
+            }
+
+            let offer = Self::counter_offer(
+                &request,
+                auth.id().unwrap(),
+                role,
+                change.price,
+                change.time,
+                change.project_scope,
+                String::new(),
+            );
+            request.push_offer(offer);
+        }
+
+        let version = request.last_modified;
+        request.last_modified = Utc::now().timestamp_micros().max(version + 1);
+
+        if !requests.replace("id", &id, version, &request).await? {
+            return Err(anyhow::anyhow!(
+                "Request was changed by another user, reload it and try again"
+            )
+            .code(409));
+        }
+
+        if !changed.is_empty() {
+            let message = format!("changed {}", changed.join(", "));
+            ActivityService::new(self.context.clone())
+                .record(request.id, ActivityKind::RequestChanged, message)
+                .await;
+        }
+
+        let public_request = PublicRequest::new(&self.context, request).await?;
+
+        Ok(public_request)
+    }
+
+    /// New offer of `author`, terms that are not given are kept from the latest offer.
+    fn counter_offer(
+        request: &AuditRequest<ObjectId>,
+        author: ObjectId,
+        role: Role,
+        price: Option<i64>,
+        time: Option<TimeRange>,
+        scope: Option<Vec<String>>,
+        message: String,
+    ) -> Offer<ObjectId> {
+        let latest_scope = request
+            .latest_offer()
+            .map(|offer| offer.scope.clone())
+            .unwrap_or_default();
+
+        Offer {
+            author,
+            role,
+            price: price.unwrap_or(request.price),
+            time: time.unwrap_or_else(|| request.time.clone()),
+            scope: scope.unwrap_or(latest_scope),
+            message,
+            status: OfferStatus::Pending,
+            timestamp: Utc::now().timestamp(),
+        }
+    }
+
+    pub async fn respond(
+        &self,
+        id: ObjectId,
+        answer: RequestOffer,
+    ) -> error::Result<PublicRequest> {
+        let auth = self.context.auth();
+
+        let requests = self
+            .context
+            .try_get_repository::<AuditRequest<ObjectId>>()?;
+
+        let Some(mut request) = requests.find("id", &Bson::ObjectId(id)).await? else {
+            return Err(anyhow::anyhow!("No request found").code(404));
         };
 
-        request.last_changer = role;
+        if !Edit.get_access(&auth, &request) {
+            return Err(anyhow::anyhow!("User is not available to answer this request").code(403));
+        }
 
-        request.last_modified = Utc::now().timestamp_micros();
+        let (user_id, role, receiver) = match auth.id() {
+            Some(user_id) if user_id == request.customer_id => {
+                (user_id, Role::Customer, request.auditor_id)
+            }
+            Some(user_id) if user_id == request.auditor_id => {
+                (user_id, Role::Auditor, request.customer_id)
+            }
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Only the customer or the auditor can answer this request"
+                )
+                .code(403))
+            }
+        };
 
-        requests.delete("id", &id).await?;
-        requests.insert(&request).await?;
+        let message = match answer.action {
+            OfferAction::Accept | OfferAction::Reject => {
+                let Some(latest) = request.offers.last_mut() else {
+                    return Err(anyhow::anyhow!("Request has no offer to answer").code(400));
+                };
+
+                if latest.status != OfferStatus::Pending || latest.author == user_id {
+                    return Err(anyhow::anyhow!(
+                        "Only a pending offer of the other side can be answered"
+                    )
+                    .code(400));
+                }
+
+                if answer.action == OfferAction::Accept {
+                    latest.status = OfferStatus::Accepted;
+                    format!("accepted the offer with price {}", latest.price)
+                } else {
+                    latest.status = OfferStatus::Rejected;
+                    format!("rejected the offer with price {}", latest.price)
+                }
+            }
+            OfferAction::Counter => {
+                validate_terms(
+                    answer.price,
+                    answer.time.as_ref(),
+                    None,
+                    answer.scope.as_deref(),
+                    None,
+                )?;
+
+                let offer = Self::counter_offer(
+                    &request,
+                    user_id,
+                    role,
+                    answer.price,
+                    answer.time,
+                    answer.scope,
+                    answer.message,
+                );
+                let message = format!("sent a counter-offer with price {}", offer.price);
+                request.push_offer(offer);
+                message
+            }
+        };
+
+        let version = request.last_modified;
+        request.last_modified = Utc::now().timestamp_micros().max(version + 1);
+
+        if !requests.replace("id", &id, version, &request).await? {
+            return Err(anyhow::anyhow!(
+                "Request was changed by another user, reload it and try again"
+            )
+            .code(409));
+        }
+
+        ActivityService::new(self.context.clone())
+            .record(request.id, ActivityKind::RequestChanged, message)
+            .await;
 
         let public_request = PublicRequest::new(&self.context, request).await?;
 
+        let event = PublicEvent::new(receiver, EventPayload::NewRequest(public_request.clone()));
+        post_event(&self.context, event, self.context.server_auth()).await?;
+
+        Ok(public_request)
+    }
+
+    /// Hands a request sent to an organization over to one of its members.
+    pub async fn assign(
+        &self,
+        id: ObjectId,
+        assign: AssignRequest,
+    ) -> error::Result<PublicRequest> {
+        let auth = self.context.auth();
+
+        let requests = self
+            .context
+            .try_get_repository::<AuditRequest<ObjectId>>()?;
+
+        let Some(mut request) = requests.find("id", &Bson::ObjectId(id)).await? else {
+            return Err(anyhow::anyhow!("No request found").code(404));
+        };
+
+        let Some(organization_id) = request.organization_id else {
+            return Err(anyhow::anyhow!("Request was not sent to an organization").code(400));
+        };
+
+        let organization =
+            request_organization(&self.context, organization_id, self.context.server_auth())
+                .await?;
+
+        let is_admin = auth.id().is_some_and(|user_id| {
+            organization.members.iter().any(|member| {
+                member.user_id == user_id.to_hex() && member.role == OrganizationRole::Admin
+            })
+        });
+
+        if !is_admin {
+            return Err(
+                anyhow::anyhow!("Only organization admins can assign this request").code(403),
+            );
+        }
+
+        if !organization
+            .members
+            .iter()
+            .any(|member| member.user_id == assign.auditor_id)
+        {
+            return Err(anyhow::anyhow!("Auditor is not a member of this organization").code(400));
+        }
+
+        let auditor_id: ObjectId = assign.auditor_id.parse()?;
+        let previous_auditor_id = request.auditor_id;
+
+        request.auditor_id = auditor_id;
+        request.team.retain(|member| member.user_id != auditor_id);
+
+        let version = request.last_modified;
+        request.last_modified = Utc::now().timestamp_micros().max(version + 1);
+
+        if !requests.replace("id", &id, version, &request).await? {
+            return Err(anyhow::anyhow!(
+                "Request was changed by another user, reload it and try again"
+            )
+            .code(409));
+        }
+
+        ActivityService::new(self.context.clone())
+            .record(
+                request.id,
+                ActivityKind::RequestChanged,
+                format!("assigned the request to {}", auditor_id),
+            )
+            .await;
+
+        let mut receivers = vec![request.customer_id, auditor_id];
+        if previous_auditor_id != auditor_id {
+            receivers.push(previous_auditor_id);
+        }
+
+        let public_request = PublicRequest::new(&self.context, request).await?;
+
+        for receiver in receivers {
+            let event =
+                PublicEvent::new(receiver, EventPayload::NewRequest(public_request.clone()));
+            post_event(&self.context, event, self.context.server_auth()).await?;
+        }
+
         Ok(public_request)
     }
 
@@ -360,15 +761,20 @@ impl RequestService {
             .context
             .try_get_repository::<AuditRequest<ObjectId>>()?;
 
-        let Some(request) = requests.delete("id", &id).await? else {
+        let Some(request) = requests.find("id", &Bson::ObjectId(id)).await? else {
             return Err(anyhow::anyhow!("No customer found").code(404));
         };
 
         if !Edit.get_access(&auth, &request) {
-            requests.insert(&request).await?;
             return Err(anyhow::anyhow!("User is not available to delete this customer").code(400));
         }
 
+        requests.delete("id", &id).await?;
+
+        ActivityService::new(self.context.clone())
+            .record(id, ActivityKind::RequestDeleted, "declined the request")
+            .await;
+
         let current_role = if auth.id() == Some(request.customer_id) {
             Role::Customer
         } else if auth.id() == Some(request.auditor_id) {
@@ -421,6 +827,108 @@ impl RequestService {
         Ok(public_request)
     }
 
+    async fn expire(&self, request: AuditRequest<ObjectId>) -> error::Result<()> {
+        let requests = self
+            .context
+            .try_get_repository::<AuditRequest<ObjectId>>()?;
+
+        let public_request = PublicRequest::new(&self.context, request.clone()).await?;
+
+        requests.delete("id", &request.id).await?;
+
+        ActivityService::new(self.context.clone())
+            .record(request.id, ActivityKind::RequestDeleted, "request expired")
+            .await;
+
+        let mut new_notification: NewNotification = serde_json::from_str(include_str!(
+            "../../templates/audit_request_decline_customer.txt"
+        ))?;
+        new_notification.user_id = Some(request.customer_id);
+
+        let variables = vec![
+            ("audit".to_owned(), public_request.project_name.clone()),
+            ("reason".to_owned(), "nobody answered it in time".to_owned()),
+        ];
+
+        send_notification(&self.context, true, true, new_notification, variables).await?;
+
+        for receiver in [request.customer_id, request.auditor_id] {
+            let event = PublicEvent::new(
+                receiver,
+                EventPayload::RequestDecline(public_request.id.clone()),
+            );
+            post_event(&self.context, event, self.context.server_auth()).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Deletes requests past their `expires_at`. Requests without it are only
+    /// expired when `max_age` is given, after that many seconds without changes.
+    pub async fn expire_stale(&self, max_age: Option<i64>) -> error::Result<usize> {
+        let requests = self
+            .context
+            .try_get_repository::<AuditRequest<ObjectId>>()?;
+
+        // `expires_at` is in milliseconds, `last_modified` in microseconds
+        let now = Utc::now();
+        let is_stale = |request: &AuditRequest<ObjectId>| match (request.expires_at, max_age) {
+            (Some(expires_at), _) => expires_at <= now.timestamp_millis(),
+            (None, Some(max_age)) => {
+                request.last_modified + max_age * 1_000_000 <= now.timestamp_micros()
+            }
+            (None, None) => false,
+        };
+
+        let mut filter = doc! {"expires_at": {"$lte": now.timestamp_millis()}};
+        if let Some(max_age) = max_age {
+            let modified_before = now.timestamp_micros() - max_age * 1_000_000;
+            filter = doc! {"$or": [
+                filter,
+                {"expires_at": null, "last_modified": {"$lte": modified_before}},
+            ]};
+        }
+
+        let mut stale = Vec::new();
+        let mut skip = 0;
+
+        loop {
+            let (page, _) = requests
+                .find_page(filter.clone(), doc! {"last_modified": 1}, skip, PAGE_SIZE)
+                .await?;
+
+            stale.extend(
+                page.iter()
+                    .filter(|request| is_stale(request))
+                    .map(|request| request.id),
+            );
+
+            if page.len() < PAGE_SIZE as usize {
+                break;
+            }
+            skip += PAGE_SIZE;
+        }
+
+        let mut expired = 0;
+
+        for id in stale {
+            // It could be answered after the scan
+            let Some(request) = requests.find("id", &Bson::ObjectId(id)).await? else {
+                continue;
+            };
+            if !is_stale(&request) {
+                continue;
+            }
+
+            match self.expire(request).await {
+                Ok(()) => expired += 1,
+                Err(err) => log::warn!("Failed to expire request {}: {}", id, err),
+            }
+        }
+
+        Ok(expired)
+    }
+
     pub async fn find_all(
         &self,
         role: Role,
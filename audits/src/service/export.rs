@@ -0,0 +1,190 @@
+use common::{
+    api::issue::PublicIssue,
+    entities::issue::{Severity, Status},
+};
+use serde_json::{json, Value};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::Major => "error",
+        Severity::Medium => "warning",
+        Severity::Minor => "note",
+        Severity::Informational => "none",
+    }
+}
+
+// Links in the `path:line` or `path:start-end` form get a region, anything else is kept as is
+fn sarif_location(link: &str) -> Value {
+    let region = link.rsplit_once(':').and_then(|(path, lines)| {
+        let (start, end) = lines.split_once('-').unwrap_or((lines, lines));
+        Some((path, start.parse::<u64>().ok()?, end.parse::<u64>().ok()?))
+    });
+
+    match region {
+        Some((path, start, end)) => json!({
+            "physicalLocation": {
+                "artifactLocation": { "uri": path },
+                "region": { "startLine": start, "endLine": end },
+            }
+        }),
+        None => json!({
+            "physicalLocation": {
+                "artifactLocation": { "uri": link },
+            }
+        }),
+    }
+}
+
+pub fn to_sarif(issues: &[PublicIssue]) -> Value {
+    let rules: Vec<Value> = issues
+        .iter()
+        .map(|issue| {
+            let mut properties = json!({ "tags": [issue.category] });
+            if let Some(cvss) = &issue.cvss {
+                properties["security-severity"] = json!(format!("{:.1}", cvss.score));
+            }
+
+            json!({
+                "id": issue.code,
+                "name": issue.name,
+                "shortDescription": { "text": issue.name },
+                "fullDescription": { "text": issue.description },
+                "defaultConfiguration": { "level": sarif_level(issue.severity) },
+                "properties": properties,
+            })
+        })
+        .collect();
+
+    let results: Vec<Value> = issues
+        .iter()
+        .enumerate()
+        .map(|(idx, issue)| {
+            let locations: Vec<Value> = issue
+                .locations
+                .iter()
+                .map(|location| {
+                    json!({
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": location.path },
+                            "region": {
+                                "startLine": location.start_line,
+                                "endLine": location.end_line,
+                            },
+                        },
+                        "properties": { "repo": location.repo, "commit": location.commit },
+                    })
+                })
+                .chain(issue.links.iter().map(|link| sarif_location(link)))
+                .collect();
+
+            json!({
+                "ruleId": issue.code,
+                "ruleIndex": idx,
+                "level": sarif_level(issue.severity),
+                "message": { "text": issue.name, "markdown": issue.description },
+                "locations": locations,
+                "properties": {
+                    "severity": issue.severity.to_string(),
+                    "status": format!("{:?}", issue.status),
+                },
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "AuditDB",
+                    "informationUri": "https://auditdb.io",
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    })
+}
+
+// Spreadsheets run cells starting with these as formulas, a leading quote keeps them text
+const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(FORMULA_PREFIXES) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+pub fn to_csv(issues: &[PublicIssue]) -> String {
+    let mut csv = String::from("code,name,severity,cvss,status,category,links,description\r\n");
+
+    for issue in issues {
+        let cvss = issue
+            .cvss
+            .as_ref()
+            .map(|cvss| format!("{:.1} {}", cvss.score, cvss.vector))
+            .unwrap_or_default();
+
+        let row = [
+            issue.code.clone(),
+            issue.name.clone(),
+            issue.severity.to_string(),
+            cvss,
+            format!("{:?}", issue.status),
+            issue.category.clone(),
+            issue
+                .locations
+                .iter()
+                .map(|location| location.url())
+                .chain(issue.links.iter().cloned())
+                .collect::<Vec<_>>()
+                .join(" "),
+            issue.description.clone(),
+        ];
+
+        let row: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push_str("\r\n");
+    }
+
+    csv
+}
+
+pub fn to_markdown(project_name: &str, issues: &[PublicIssue]) -> String {
+    let mut markdown = format!("# {} issues\n", project_name);
+
+    for issue in issues {
+        let checked = if issue.status == Status::Fixed {
+            "x"
+        } else {
+            " "
+        };
+
+        markdown.push_str(&format!(
+            "\n- [{}] **{}** {} ({}, {:?})",
+            checked, issue.code, issue.name, issue.severity, issue.status
+        ));
+
+        for location in &issue.locations {
+            markdown.push_str(&format!("\n  - [{}]({})", location, location.url()));
+        }
+
+        for link in &issue.links {
+            markdown.push_str(&format!("\n  - {}", link));
+        }
+    }
+
+    markdown.push('\n');
+    markdown
+}
@@ -0,0 +1,238 @@
+use chrono::Utc;
+use common::{
+    access_rules::{AccessRules, Edit, Read},
+    api::{
+        audits::{ChangeFindingTemplate, CreateFindingTemplate},
+        organization::{request_my_organizations, request_organization},
+    },
+    context::GeneralContext,
+    entities::{finding_template::FindingTemplate, organization::OrganizationRole},
+    error::{self, AddCode},
+};
+use mongodb::bson::{oid::ObjectId, Bson};
+
+pub struct FindingTemplateService {
+    context: GeneralContext,
+}
+
+impl FindingTemplateService {
+    #[must_use]
+    pub const fn new(context: GeneralContext) -> Self {
+        Self { context }
+    }
+
+    /// Whether the user is a member (or an admin, if `admin` is set) of the template's organization.
+    async fn organization_access(
+        &self,
+        template: &FindingTemplate<ObjectId>,
+        admin: bool,
+    ) -> error::Result<bool> {
+        let (Some(organization_id), Some(user_id)) =
+            (template.organization_id, self.context.auth().id())
+        else {
+            return Ok(false);
+        };
+
+        let organization =
+            request_organization(&self.context, organization_id, self.context.server_auth())
+                .await?;
+
+        Ok(organization.members.iter().any(|member| {
+            member.user_id == user_id.to_hex() && (!admin || member.role == OrganizationRole::Admin)
+        }))
+    }
+
+    pub async fn create(
+        &self,
+        template: CreateFindingTemplate,
+    ) -> error::Result<FindingTemplate<String>> {
+        let auth = self.context.auth();
+
+        let templates = self
+            .context
+            .try_get_repository::<FindingTemplate<ObjectId>>()?;
+
+        let Some(auditor_id) = auth.id() else {
+            return Err(anyhow::anyhow!("Template can be created only by auditor").code(403));
+        };
+
+        if template.name.trim().is_empty() {
+            return Err(anyhow::anyhow!("Template name is empty").code(400));
+        }
+
+        let organization_id = match &template.organization_id {
+            Some(organization_id) => {
+                let organization = request_organization(
+                    &self.context,
+                    organization_id.parse()?,
+                    self.context.server_auth(),
+                )
+                .await?;
+
+                if !organization
+                    .members
+                    .iter()
+                    .any(|member| member.user_id == auditor_id.to_hex())
+                {
+                    return Err(
+                        anyhow::anyhow!("User is not a member of this organization").code(403)
+                    );
+                }
+
+                Some(organization_id.parse()?)
+            }
+            None => None,
+        };
+
+        let template = FindingTemplate {
+            id: ObjectId::new(),
+            auditor_id,
+            name: template.name,
+            category: template.category,
+            severity: template.severity,
+            description: template.description,
+            remediation: template.remediation,
+            last_modified: Utc::now().timestamp_micros(),
+            organization_id,
+        };
+
+        templates.insert(&template).await?;
+
+        Ok(template.stringify())
+    }
+
+    pub async fn get(&self, id: ObjectId) -> error::Result<FindingTemplate<ObjectId>> {
+        let auth = self.context.auth();
+
+        let templates = self
+            .context
+            .try_get_repository::<FindingTemplate<ObjectId>>()?;
+
+        let Some(template) = templates.find("id", &Bson::ObjectId(id)).await? else {
+            return Err(anyhow::anyhow!("No template found").code(404));
+        };
+
+        if !Read.get_access(&auth, &template) && !self.organization_access(&template, false).await?
+        {
+            return Err(anyhow::anyhow!("User is not available to read this template").code(403));
+        }
+
+        Ok(template)
+    }
+
+    pub async fn find(&self, id: ObjectId) -> error::Result<FindingTemplate<String>> {
+        Ok(self.get(id).await?.stringify())
+    }
+
+    pub async fn my_templates(&self) -> error::Result<Vec<FindingTemplate<String>>> {
+        let auth = self.context.auth();
+
+        let templates = self
+            .context
+            .try_get_repository::<FindingTemplate<ObjectId>>()?;
+
+        let Some(auditor_id) = auth.id() else {
+            return Err(anyhow::anyhow!("No user found").code(403));
+        };
+
+        let mut my_templates = templates
+            .find_many("auditor_id", &Bson::ObjectId(auditor_id))
+            .await?;
+
+        for organization in request_my_organizations(&self.context, auth).await? {
+            let shared = templates
+                .find_many("organization_id", &Bson::ObjectId(organization.id.parse()?))
+                .await?;
+
+            for template in shared {
+                if !my_templates.iter().any(|mine| mine.id == template.id) {
+                    my_templates.push(template);
+                }
+            }
+        }
+
+        let mut templates = my_templates;
+
+        templates.sort_by(|a, b| a.category.cmp(&b.category).then(a.name.cmp(&b.name)));
+
+        Ok(templates
+            .into_iter()
+            .map(FindingTemplate::stringify)
+            .collect())
+    }
+
+    pub async fn change(
+        &self,
+        id: ObjectId,
+        change: ChangeFindingTemplate,
+    ) -> error::Result<FindingTemplate<String>> {
+        let auth = self.context.auth();
+
+        let templates = self
+            .context
+            .try_get_repository::<FindingTemplate<ObjectId>>()?;
+
+        let Some(mut template) = templates.find("id", &Bson::ObjectId(id)).await? else {
+            return Err(anyhow::anyhow!("No template found").code(404));
+        };
+
+        if !Edit.get_access(&auth, &template) && !self.organization_access(&template, true).await? {
+            return Err(anyhow::anyhow!("User is not available to change this template").code(403));
+        }
+
+        if let Some(name) = change.name {
+            if name.trim().is_empty() {
+                return Err(anyhow::anyhow!("Template name is empty").code(400));
+            }
+            template.name = name;
+        }
+
+        if let Some(category) = change.category {
+            template.category = category;
+        }
+
+        if let Some(severity) = change.severity {
+            template.severity = severity;
+        }
+
+        if let Some(description) = change.description {
+            template.description = description;
+        }
+
+        if let Some(remediation) = change.remediation {
+            template.remediation = remediation;
+        }
+
+        let version = template.last_modified;
+        template.last_modified = Utc::now().timestamp_micros().max(version + 1);
+
+        if !templates.replace("id", &id, version, &template).await? {
+            return Err(anyhow::anyhow!(
+                "Template was changed by another user, reload it and try again"
+            )
+            .code(409));
+        }
+
+        Ok(template.stringify())
+    }
+
+    pub async fn delete(&self, id: ObjectId) -> error::Result<FindingTemplate<String>> {
+        let auth = self.context.auth();
+
+        let templates = self
+            .context
+            .try_get_repository::<FindingTemplate<ObjectId>>()?;
+
+        let Some(template) = templates.find("id", &Bson::ObjectId(id)).await? else {
+            return Err(anyhow::anyhow!("No template found").code(404));
+        };
+
+        if !Edit.get_access(&auth, &template) && !self.organization_access(&template, true).await? {
+            return Err(anyhow::anyhow!("User is not available to delete this template").code(403));
+        }
+
+        templates.delete("id", &id).await?;
+
+        Ok(template.stringify())
+    }
+}
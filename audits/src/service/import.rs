@@ -0,0 +1,437 @@
+use std::collections::HashMap;
+
+use common::{
+    api::audits::{CreateIssue, ImportFormat, ImportParams},
+    entities::issue::{CodeLocation, Severity, Status},
+    error::{self, AddCode},
+};
+use serde::Deserialize;
+use serde_json::Value;
+
+const SARIF_VERSION: &str = "2.1.0";
+
+#[derive(Debug, Deserialize)]
+struct SarifLog {
+    version: String,
+    #[serde(default)]
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRun {
+    tool: SarifTool,
+    #[serde(default)]
+    results: Vec<SarifResult>,
+    #[serde(default)]
+    version_control_provenance: Vec<SarifVersionControl>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifVersionControl {
+    repository_uri: String,
+    revision_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifDriver {
+    #[serde(default)]
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule {
+    id: String,
+    name: Option<String>,
+    short_description: Option<SarifMessage>,
+    default_configuration: Option<SarifConfiguration>,
+    #[serde(default)]
+    properties: HashMap<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifMessage {
+    text: Option<String>,
+    markdown: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifConfiguration {
+    level: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: Option<String>,
+    rule_index: Option<usize>,
+    level: Option<String>,
+    message: SarifMessage,
+    #[serde(default)]
+    locations: Vec<SarifLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    physical_location: Option<SarifPhysicalLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: Option<SarifArtifactLocation>,
+    region: Option<SarifRegion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: Option<u64>,
+    end_line: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlitherOutput {
+    #[serde(default)]
+    success: bool,
+    error: Option<String>,
+    results: Option<SlitherResults>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlitherResults {
+    #[serde(default)]
+    detectors: Vec<SlitherDetector>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlitherDetector {
+    check: String,
+    impact: String,
+    description: String,
+    #[serde(default)]
+    elements: Vec<SlitherElement>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlitherElement {
+    source_mapping: Option<SlitherSourceMapping>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlitherSourceMapping {
+    filename_relative: String,
+    #[serde(default)]
+    lines: Vec<u64>,
+}
+
+struct FindingLocation {
+    path: String,
+    start_line: Option<u64>,
+    end_line: Option<u64>,
+}
+
+impl FindingLocation {
+    fn new(path: &str, start_line: Option<u64>, end_line: Option<u64>) -> Self {
+        Self {
+            path: path.trim_start_matches("file://").to_string(),
+            start_line: start_line.filter(|line| *line > 0),
+            end_line,
+        }
+    }
+
+    fn to_code_location(&self, repo: &str, commit: &str) -> Option<CodeLocation> {
+        let start_line = self.start_line?;
+        Some(CodeLocation {
+            repo: repo.to_string(),
+            commit: commit.to_string(),
+            path: self.path.trim_start_matches("./").to_string(),
+            start_line,
+            end_line: self.end_line.unwrap_or(start_line).max(start_line),
+        })
+    }
+}
+
+impl std::fmt::Display for FindingLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match (self.start_line, self.end_line) {
+            (Some(start), Some(end)) if end > start => write!(f, "{}:{}-{}", self.path, start, end),
+            (Some(start), _) => write!(f, "{}:{}", self.path, start),
+            _ => write!(f, "{}", self.path),
+        }
+    }
+}
+
+struct Finding {
+    rule: String,
+    title: String,
+    message: String,
+    severity: Severity,
+    locations: Vec<FindingLocation>,
+    /// Repository and commit the report names for its paths
+    repo: Option<String>,
+    commit: Option<String>,
+}
+
+// Same thresholds as GitHub code scanning uses for `security-severity`
+fn sarif_severity(rule: Option<&SarifRule>, level: Option<&str>) -> Severity {
+    let security_severity = rule
+        .and_then(|rule| rule.properties.get("security-severity"))
+        .and_then(|value| match value {
+            Value::String(value) => value.parse::<f64>().ok(),
+            value => value.as_f64(),
+        });
+
+    if let Some(score) = security_severity {
+        return match score {
+            score if score >= 9.0 => Severity::Critical,
+            score if score >= 7.0 => Severity::Major,
+            score if score >= 4.0 => Severity::Medium,
+            score if score > 0.0 => Severity::Minor,
+            _ => Severity::Informational,
+        };
+    }
+
+    let level = level.or_else(|| {
+        rule.and_then(|rule| rule.default_configuration.as_ref())
+            .and_then(|configuration| configuration.level.as_deref())
+    });
+
+    match level {
+        Some("error") => Severity::Major,
+        Some("note") => Severity::Minor,
+        Some("none") => Severity::Informational,
+        _ => Severity::Medium,
+    }
+}
+
+fn parse_sarif(report: Value) -> error::Result<Vec<Finding>> {
+    let log: SarifLog = serde_json::from_value(report)?;
+
+    if log.version != SARIF_VERSION {
+        return Err(anyhow::anyhow!("Unsupported SARIF version {}", log.version).code(400));
+    }
+
+    let mut findings = Vec::new();
+
+    for run in log.runs {
+        let rules = &run.tool.driver.rules;
+        let provenance = run.version_control_provenance.first();
+
+        for result in run.results {
+            let rule = result
+                .rule_index
+                .and_then(|index| rules.get(index))
+                .or_else(|| {
+                    result
+                        .rule_id
+                        .as_ref()
+                        .and_then(|id| rules.iter().find(|rule| &rule.id == id))
+                });
+
+            let Some(rule_id) = result
+                .rule_id
+                .clone()
+                .or_else(|| rule.map(|rule| rule.id.clone()))
+            else {
+                return Err(anyhow::anyhow!("SARIF result without a rule").code(400));
+            };
+
+            let title = rule
+                .and_then(|rule| {
+                    rule.short_description
+                        .as_ref()
+                        .and_then(|description| description.text.clone())
+                        .or_else(|| rule.name.clone())
+                })
+                .unwrap_or_else(|| rule_id.clone());
+
+            let locations = result
+                .locations
+                .iter()
+                .filter_map(|location| location.physical_location.as_ref())
+                .filter_map(|location| {
+                    let uri = &location.artifact_location.as_ref()?.uri;
+                    let region = location.region.as_ref();
+                    Some(FindingLocation::new(
+                        uri,
+                        region.and_then(|region| region.start_line),
+                        region.and_then(|region| region.end_line),
+                    ))
+                })
+                .collect();
+
+            findings.push(Finding {
+                severity: sarif_severity(rule, result.level.as_deref()),
+                rule: rule_id,
+                title,
+                message: result
+                    .message
+                    .markdown
+                    .or(result.message.text)
+                    .unwrap_or_default(),
+                locations,
+                repo: provenance.map(|provenance| provenance.repository_uri.clone()),
+                commit: provenance.and_then(|provenance| provenance.revision_id.clone()),
+            });
+        }
+    }
+
+    Ok(findings)
+}
+
+fn slither_severity(impact: &str) -> Severity {
+    match impact {
+        "High" => Severity::Major,
+        "Medium" => Severity::Medium,
+        "Low" => Severity::Minor,
+        _ => Severity::Informational,
+    }
+}
+
+fn parse_slither(report: Value) -> error::Result<Vec<Finding>> {
+    let output: SlitherOutput = serde_json::from_value(report)?;
+
+    if !output.success {
+        let error = output.error.unwrap_or_default();
+        return Err(anyhow::anyhow!("Slither run has failed: {}", error).code(400));
+    }
+
+    let detectors = output
+        .results
+        .map(|results| results.detectors)
+        .unwrap_or_default();
+
+    let findings = detectors
+        .into_iter()
+        .map(|detector| {
+            let locations = detector
+                .elements
+                .iter()
+                .filter_map(|element| element.source_mapping.as_ref())
+                .map(|mapping| {
+                    FindingLocation::new(
+                        &mapping.filename_relative,
+                        mapping.lines.iter().min().copied(),
+                        mapping.lines.iter().max().copied(),
+                    )
+                })
+                .collect();
+
+            Finding {
+                severity: slither_severity(&detector.impact),
+                title: detector.check.clone(),
+                rule: detector.check,
+                message: detector.description.trim().to_string(),
+                locations,
+                repo: None,
+                commit: None,
+            }
+        })
+        .collect();
+
+    Ok(findings)
+}
+
+/// Parses the tool output and groups findings of the same rule into one draft issue.
+/// Findings become code locations when the repository and commit are known and in
+/// the scope, the rest is kept as links.
+/// Returns the draft issues together with the number of findings in the report.
+pub fn parse_report(
+    params: &ImportParams,
+    report: Value,
+    scope: &[String],
+) -> error::Result<(Vec<CreateIssue>, usize)> {
+    let findings = match params.format {
+        ImportFormat::Sarif => parse_sarif(report)?,
+        ImportFormat::Slither => parse_slither(report)?,
+    };
+
+    let total = findings.len();
+    let mut issues: Vec<(String, CreateIssue, Vec<String>)> = Vec::new();
+
+    for finding in findings {
+        let position = issues.iter().position(|(rule, _, _)| rule == &finding.rule);
+        let (_, issue, messages) = match position {
+            Some(position) => &mut issues[position],
+            None => {
+                issues.push((
+                    finding.rule.clone(),
+                    CreateIssue {
+                        name: finding.title,
+                        description: String::new(),
+                        status: Status::Draft,
+                        severity: finding.severity,
+                        cvss: None,
+                        category: finding.rule,
+                        links: Vec::new(),
+                        locations: Vec::new(),
+                        feedback: None,
+                    },
+                    Vec::new(),
+                ));
+                issues.last_mut().unwrap()
+            }
+        };
+
+        issue.severity = issue.severity.min(finding.severity);
+
+        let repo = finding.repo.as_deref().or(params.repo.as_deref());
+        let commit = finding.commit.as_deref().or(params.commit.as_deref());
+
+        for location in finding.locations {
+            let code_location = repo
+                .zip(commit)
+                .and_then(|(repo, commit)| location.to_code_location(repo, commit))
+                .filter(|code_location| code_location.validate(scope).is_ok());
+
+            match code_location {
+                Some(code_location) => {
+                    if !issue.locations.contains(&code_location) {
+                        issue.locations.push(code_location);
+                    }
+                }
+                None => {
+                    let link = location.to_string();
+                    if !issue.links.contains(&link) {
+                        issue.links.push(link);
+                    }
+                }
+            }
+        }
+
+        if !finding.message.is_empty() {
+            messages.push(finding.message);
+        }
+    }
+
+    let issues = issues
+        .into_iter()
+        .map(|(_, mut issue, messages)| {
+            issue.description = match messages.len() {
+                1 => messages.into_iter().next().unwrap(),
+                _ => messages
+                    .iter()
+                    .map(|message| format!("- {}", message.replace('\n', "\n  ")))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            };
+            issue
+        })
+        .collect();
+
+    Ok((issues, total))
+}
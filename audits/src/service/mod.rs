@@ -1,2 +1,8 @@
+pub mod activity;
+pub mod analytics;
 pub mod audit;
 pub mod audit_request;
+pub mod export;
+pub mod finding_template;
+pub mod import;
+pub mod reminder;
@@ -0,0 +1,197 @@
+use chrono::Utc;
+use common::{
+    api::{send_notification, NewNotification},
+    context::GeneralContext,
+    entities::audit::{Audit, AuditStatus, ReminderKind, SentReminder},
+    error,
+};
+use mongodb::bson::{doc, oid::ObjectId, to_bson};
+
+use super::audit::AuditService;
+
+const PAGE_SIZE: u32 = 100;
+
+/// How long before the start and the end of an audit its auditor is reminded, in seconds.
+#[derive(Debug, Clone, Copy)]
+pub struct ReminderOffsets {
+    pub start: i64,
+    pub finish: i64,
+}
+
+impl Default for ReminderOffsets {
+    fn default() -> Self {
+        Self {
+            start: 2 * 24 * 60 * 60,
+            finish: 2 * 24 * 60 * 60,
+        }
+    }
+}
+
+fn time_left(millis: i64) -> String {
+    let hours = (millis + 3_599_999) / 3_600_000;
+    let (amount, unit) = if hours >= 24 {
+        ((hours + 12) / 24, "day")
+    } else {
+        (hours.max(1), "hour")
+    };
+
+    if amount == 1 {
+        format!("1 {}", unit)
+    } else {
+        format!("{} {}s", amount, unit)
+    }
+}
+
+pub struct ReminderService {
+    context: GeneralContext,
+}
+
+impl ReminderService {
+    #[must_use]
+    pub const fn new(context: GeneralContext) -> Self {
+        Self { context }
+    }
+
+    /// Reminders the audit should have received by `now` (in milliseconds, like `Audit.time`).
+    fn due_reminders(
+        audit: &Audit<ObjectId>,
+        offsets: ReminderOffsets,
+        now: i64,
+    ) -> Vec<SentReminder> {
+        if audit.no_customer {
+            return Vec::new();
+        }
+
+        let mut due = Vec::new();
+
+        match audit.status {
+            AuditStatus::Waiting
+                if now < audit.time.from && audit.time.from - now <= offsets.start * 1000 =>
+            {
+                due.push(SentReminder {
+                    kind: ReminderKind::AboutToStart,
+                    at: audit.time.from,
+                });
+            }
+            AuditStatus::Started
+                if now < audit.time.to && audit.time.to - now <= offsets.finish * 1000 =>
+            {
+                due.push(SentReminder {
+                    kind: ReminderKind::AboutToFinish,
+                    at: audit.time.to,
+                });
+            }
+            AuditStatus::Review => {
+                let submitted = audit
+                    .status_history
+                    .iter()
+                    .rev()
+                    .find(|transition| transition.to == AuditStatus::Review);
+
+                if let Some(submitted) = submitted {
+                    due.push(SentReminder {
+                        kind: ReminderKind::Submitted,
+                        at: submitted.timestamp / 1000,
+                    });
+                }
+            }
+            _ => {}
+        }
+
+        due.retain(|reminder| !audit.reminders.contains(reminder));
+        due
+    }
+
+    /// Marks the reminder as sent before sending it, so a restart never sends it twice.
+    async fn send_reminder(
+        &self,
+        audit: &Audit<ObjectId>,
+        reminder: SentReminder,
+        now: i64,
+    ) -> error::Result<()> {
+        let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
+
+        audits
+            .update(
+                "_id",
+                &audit.id,
+                doc! {},
+                doc! {"reminders": to_bson(&reminder)?},
+            )
+            .await?;
+
+        let (receivers, template) = match reminder.kind {
+            ReminderKind::AboutToStart => (
+                audit.auditors(),
+                include_str!("../../templates/audit_is_about_to_start.txt"),
+            ),
+            ReminderKind::AboutToFinish => (
+                audit.auditors(),
+                include_str!("../../templates/audit_is_about_to_finish.txt"),
+            ),
+            ReminderKind::Submitted => (
+                vec![audit.customer_id],
+                include_str!("../../templates/audit_submitted.txt"),
+            ),
+        };
+
+        let project_name = AuditService::new(self.context.clone())
+            .project_name(audit)
+            .await?;
+
+        for receiver_id in receivers {
+            let mut new_notification: NewNotification = serde_json::from_str(template)?;
+            new_notification.user_id = Some(receiver_id);
+
+            // A submission is in the past, only upcoming dates have time left
+            let mut variables = vec![("audit".to_owned(), project_name.clone())];
+            if reminder.at > now {
+                variables.push(("time_left".to_owned(), time_left(reminder.at - now)));
+            }
+
+            send_notification(&self.context, true, true, new_notification, variables).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn remind(&self, audit: &Audit<ObjectId>, offsets: ReminderOffsets) -> error::Result<()> {
+        let now = Utc::now().timestamp_millis();
+
+        for reminder in Self::due_reminders(audit, offsets, now) {
+            self.send_reminder(audit, reminder, now).await?;
+        }
+
+        AuditService::new(self.context.clone())
+            .notify_overdue(audit)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Scans all audits and sends the reminders and overdue notifications that are due.
+    pub async fn send_reminders(&self, offsets: ReminderOffsets) -> error::Result<usize> {
+        let audits = self.context.try_get_repository::<Audit<ObjectId>>()?;
+
+        let mut skip = 0;
+        let mut scanned = 0;
+
+        loop {
+            let page = audits.find_all(skip, PAGE_SIZE).await?;
+
+            for audit in &page {
+                if let Err(err) = self.remind(audit, offsets).await {
+                    log::warn!("Failed to send reminders for audit {}: {}", audit.id, err);
+                }
+            }
+
+            scanned += page.len();
+            if page.len() < PAGE_SIZE as usize {
+                break;
+            }
+            skip += PAGE_SIZE;
+        }
+
+        Ok(scanned)
+    }
+}
@@ -218,7 +218,7 @@ impl ChatRepository {
                     if let Some(unread) = unread {
                         user_read.unread = unread
                     } else {
-                        user_read.unread = user_read.unread.clone() + 1
+                        user_read.unread += 1
                     }
                 }
             } else {
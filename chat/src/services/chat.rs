@@ -155,13 +155,13 @@ impl ChatService {
                 }
 
                 let (name, avatar) = if id.role == Role::Auditor {
-                    let auditor = request_auditor(&self.context, id.id, auth.clone()).await?;
+                    let auditor = request_auditor(&self.context, id.id, auth).await?;
                     (
                         auditor.first_name().clone() + " " + auditor.last_name(),
                         auditor.avatar().to_string(),
                     )
                 } else {
-                    let customer = request_customer(&self.context, id.id, auth.clone()).await?;
+                    let customer = request_customer(&self.context, id.id, auth).await?;
                     (
                         customer.first_name + " " + &customer.last_name,
                         customer.avatar,
@@ -193,7 +193,7 @@ impl ChatService {
             }
         }
 
-        chats.sort_by(|a, b| a.last_modified.cmp(&b.last_modified));
+        chats.sort_by_key(|a| a.last_modified);
 
         Ok(chats)
     }
@@ -70,7 +70,7 @@ impl ClocRepo {
         // pull
         Command::new("git")
             .arg("pull")
-            .current_dir(&self.path.join(&original.author).join(&original.repo))
+            .current_dir(self.path.join(&original.author).join(&original.repo))
             .output()
             .await?;
 
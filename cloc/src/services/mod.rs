@@ -54,6 +54,6 @@ impl ClocService {
             .output()
             .await?;
         let res: Value = serde_json::from_slice(&result.stdout)?;
-        Ok(ClocCount::parse(res)?)
+        ClocCount::parse(res)
     }
 }
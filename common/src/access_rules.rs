@@ -8,6 +8,8 @@ use crate::{
         auditor::Auditor,
         badge::Badge,
         customer::Customer,
+        finding_template::FindingTemplate,
+        organization::Organization,
         project::Project,
         user::User,
     },
@@ -23,6 +25,8 @@ pub struct Edit;
 
 pub struct Delete;
 
+pub struct Disclose;
+
 impl<'a, 'b> AccessRules<&'a Auth, &'b User<ObjectId>> for Read {
     fn get_access(&self, auth: &'a Auth, _user: &'b User<ObjectId>) -> bool {
         #[allow(clippy::match_single_binding)]
@@ -124,7 +128,11 @@ impl<'a, 'b> AccessRules<&'a Auth, &'b AuditRequest<ObjectId>> for Read {
     fn get_access(&self, auth: &'a Auth, request: &'b AuditRequest<ObjectId>) -> bool {
         match auth {
             Auth::Service(_, _) | Auth::Admin(_) => true,
-            Auth::User(id) => &request.customer_id == id || &request.auditor_id == id,
+            Auth::User(id) => {
+                &request.customer_id == id
+                    || &request.auditor_id == id
+                    || request.team.iter().any(|member| &member.user_id == id)
+            }
             Auth::None => false,
         }
     }
@@ -140,11 +148,41 @@ impl<'a, 'b> AccessRules<&'a Auth, &'b AuditRequest<ObjectId>> for Edit {
     }
 }
 
+impl<'a, 'b> AccessRules<&'a Auth, &'b Organization<ObjectId>> for Edit {
+    fn get_access(&self, auth: &'a Auth, organization: &'b Organization<ObjectId>) -> bool {
+        match auth {
+            Auth::Service(_, _) | Auth::Admin(_) => true,
+            Auth::User(id) => organization.is_admin(id),
+            Auth::None => false,
+        }
+    }
+}
+
+impl<'a, 'b> AccessRules<&'a Auth, &'b FindingTemplate<ObjectId>> for Read {
+    fn get_access(&self, auth: &'a Auth, template: &'b FindingTemplate<ObjectId>) -> bool {
+        match auth {
+            Auth::Service(_, _) | Auth::Admin(_) => true,
+            Auth::User(id) => &template.auditor_id == id,
+            Auth::None => false,
+        }
+    }
+}
+
+impl<'a, 'b> AccessRules<&'a Auth, &'b FindingTemplate<ObjectId>> for Edit {
+    fn get_access(&self, auth: &'a Auth, template: &'b FindingTemplate<ObjectId>) -> bool {
+        match auth {
+            Auth::Service(_, _) | Auth::Admin(_) => true,
+            Auth::User(id) => &template.auditor_id == id,
+            Auth::None => false,
+        }
+    }
+}
+
 impl<'a, 'b> AccessRules<&'a Auth, &'b Audit<ObjectId>> for Read {
     fn get_access(&self, auth: &'a Auth, request: &'b Audit<ObjectId>) -> bool {
         match auth {
             Auth::Service(_, _) | Auth::Admin(_) => true,
-            Auth::User(id) => &request.customer_id == id || &request.auditor_id == id,
+            Auth::User(id) => &request.customer_id == id || request.is_auditor(*id),
             Auth::None => false,
         }
     }
@@ -154,7 +192,7 @@ impl<'a, 'b> AccessRules<&'a Auth, &'b Audit<ObjectId>> for Edit {
     fn get_access(&self, auth: &'a Auth, request: &'b Audit<ObjectId>) -> bool {
         match auth {
             Auth::Service(_, _) | Auth::Admin(_) => true,
-            Auth::User(id) => &request.customer_id == id || &request.auditor_id == id,
+            Auth::User(id) => &request.customer_id == id || request.is_editor(*id),
             Auth::None => false,
         }
     }
@@ -173,6 +211,16 @@ impl<'a, 'b> AccessRules<&'a Auth, &'b Audit<ObjectId>> for Delete {
     }
 }
 
+impl<'a, 'b> AccessRules<&'a Auth, &'b Audit<ObjectId>> for Disclose {
+    fn get_access(&self, auth: &'a Auth, audit: &'b Audit<ObjectId>) -> bool {
+        match auth {
+            Auth::Service(_, _) | Auth::Admin(_) => true,
+            Auth::User(id) => audit.is_editor(*id),
+            Auth::None => false,
+        }
+    }
+}
+
 pub struct GetData;
 
 impl<'a> AccessRules<&'a Auth, ()> for GetData {
@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{
+    audit::Audit,
+    issue::{Severity, Status},
+    role::Role,
+};
+
+/// Durations are in seconds.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct IssueStatistics {
+    pub total: usize,
+    pub by_severity: BTreeMap<Severity, usize>,
+    pub by_status: BTreeMap<String, usize>,
+    pub mean_time_to_fix: Option<f64>,
+    pub mean_verification_turnaround: Option<f64>,
+    pub lines_of_code: Option<u64>,
+    pub issues_per_kloc: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AuditAnalytics {
+    pub audit_id: String,
+    pub project_name: String,
+    #[serde(flatten)]
+    pub statistics: IssueStatistics,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UserAnalytics {
+    pub user_id: String,
+    pub role: Role,
+    pub audits: usize,
+    #[serde(flatten)]
+    pub statistics: IssueStatistics,
+    pub per_audit: Vec<AuditAnalytics>,
+}
+
+/// Collects raw samples so statistics of several audits are aggregated
+/// from the samples themselves and not from per-audit means.
+#[derive(Debug, Default)]
+pub struct StatisticsBuilder {
+    total: usize,
+    by_severity: BTreeMap<Severity, usize>,
+    by_status: BTreeMap<String, usize>,
+    fix_times: Vec<i64>,
+    turnarounds: Vec<i64>,
+    lines_of_code: Option<u64>,
+    counted_issues: usize,
+}
+
+fn mean(samples: &[i64]) -> Option<f64> {
+    if samples.is_empty() {
+        None
+    } else {
+        Some(samples.iter().sum::<i64>() as f64 / samples.len() as f64)
+    }
+}
+
+impl StatisticsBuilder {
+    /// Drafts are not findings yet and excluded issues are not part of the report.
+    pub fn add_audit<Id: Eq + std::hash::Hash>(&mut self, audit: &Audit<Id>) {
+        let issues: Vec<_> = audit
+            .issues
+            .iter()
+            .filter(|issue| issue.include && issue.status != Status::Draft)
+            .collect();
+
+        for issue in &issues {
+            self.total += 1;
+            *self.by_severity.entry(issue.severity).or_default() += 1;
+            *self
+                .by_status
+                .entry(format!("{:?}", issue.status))
+                .or_default() += 1;
+
+            let history = &issue.status_history;
+
+            let disclosed = history
+                .iter()
+                .find(|transition| transition.status != Status::Draft);
+            let fixed = history
+                .iter()
+                .find(|transition| transition.status == Status::Fixed);
+
+            if let (Some(disclosed), Some(fixed)) = (disclosed, fixed) {
+                self.fix_times.push(fixed.timestamp - disclosed.timestamp);
+            }
+
+            // Time the auditor needed to answer each request for verification
+            for window in history.windows(2) {
+                if window[0].status == Status::Verification {
+                    self.turnarounds
+                        .push(window[1].timestamp - window[0].timestamp);
+                }
+            }
+        }
+
+        if let Some(lines_of_code) = audit.lines_of_code {
+            *self.lines_of_code.get_or_insert(0) += lines_of_code;
+            self.counted_issues += issues.len();
+        }
+    }
+
+    pub fn build(self) -> IssueStatistics {
+        let issues_per_kloc = self
+            .lines_of_code
+            .filter(|lines_of_code| *lines_of_code > 0)
+            .map(|lines_of_code| self.counted_issues as f64 * 1000.0 / lines_of_code as f64);
+
+        IssueStatistics {
+            total: self.total,
+            by_severity: self.by_severity,
+            by_status: self.by_status,
+            mean_time_to_fix: mean(&self.fix_times),
+            mean_verification_turnaround: mean(&self.turnarounds),
+            lines_of_code: self.lines_of_code,
+            issues_per_kloc,
+        }
+    }
+}
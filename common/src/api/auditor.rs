@@ -8,6 +8,8 @@ use crate::{
     services::{API_PREFIX, AUDITORS_SERVICE, PROTOCOL},
 };
 
+use super::badge::request_badges;
+
 pub async fn request_auditor(
     context: &GeneralContext,
     id: ObjectId,
@@ -28,3 +30,50 @@ pub async fn request_auditor(
         .json::<ExtendedAuditor>()
         .await?)
 }
+
+/// Auditors by their user ids, users who only have a badge are returned as badges.
+pub async fn request_auditors(
+    context: &GeneralContext,
+    ids: Vec<ObjectId>,
+    auth: Auth,
+) -> error::Result<Vec<ExtendedAuditor>> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let auditors = context
+        .make_request::<Vec<ObjectId>>()
+        .post(format!(
+            "{}://{}/{}/auditor/data",
+            PROTOCOL.as_str(),
+            AUDITORS_SERVICE.as_str(),
+            API_PREFIX.as_str(),
+        ))
+        .auth(auth)
+        .json(&ids)
+        .send()
+        .await?
+        .json::<Vec<PublicAuditor>>()
+        .await?;
+
+    let missing: Vec<ObjectId> = ids
+        .into_iter()
+        .filter(|id| {
+            !auditors
+                .iter()
+                .any(|auditor| auditor.user_id == id.to_hex())
+        })
+        .collect();
+
+    let badges = if missing.is_empty() {
+        Vec::new()
+    } else {
+        request_badges(context, missing, auth).await?
+    };
+
+    Ok(auditors
+        .into_iter()
+        .map(ExtendedAuditor::Auditor)
+        .chain(badges.into_iter().map(ExtendedAuditor::Badge))
+        .collect())
+}
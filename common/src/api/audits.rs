@@ -1,31 +1,36 @@
+use std::collections::HashMap;
+
 use chrono::Utc;
-use mongodb::bson::oid::ObjectId;
+use mongodb::bson::{doc, oid::ObjectId, to_bson, Document};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 use crate::{
+    auth::Auth,
     context::GeneralContext,
     entities::{
-        audit::{Audit, AuditStatus, PublicAuditStatus},
+        activity::Activity,
+        audit::{
+            Audit, AuditAction, AuditStatus, AuditTransition, PublicAuditStatus, TeamMember,
+            TeamRole,
+        },
         audit_request::TimeRange,
         auditor::{ExtendedAuditor, PublicAuditor},
         contacts::Contacts,
-        issue::{Issue, Status},
+        customer::PublicCustomer,
+        cvss::Cvss,
+        issue::{CodeLocation, Issue, IssueTransition, Severity, Status},
         project::PublicProject,
+        review_round::{IssueVerdict, ReviewRound},
+        role::Role,
     },
-    error,
+    error::{self, AddCode},
     services::{API_PREFIX, AUDITORS_SERVICE, CUSTOMERS_SERVICE, PROTOCOL},
 };
 
-use super::issue::PublicIssue;
-
-#[derive(Debug, Serialize, Deserialize)]
-pub enum AuditAction {
-    #[serde(alias = "start")]
-    Start,
-    #[serde(alias = "resolve")]
-    Resolve,
-}
+use super::{
+    auditor::request_auditors, customer::request_customers, issue::PublicIssue,
+    project::request_projects, seartch::SortOrder,
+};
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct AuditChange {
@@ -41,6 +46,76 @@ pub struct AuditChange {
     pub start_audit: Option<bool>,
     #[serde(rename = "isPublic")]
     pub public: Option<bool>,
+    /// Replaces the team, only the lead auditor can change it.
+    pub team: Option<Vec<TeamMember<String>>>,
+}
+
+/// Filters of `my_audit`, `from` and `to` select audits whose time range overlaps them.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct AuditFilter {
+    pub status: Option<AuditStatus>,
+    pub tag: Option<String>,
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    pub sort: Option<SortOrder>,
+}
+
+/// Matches audits and requests of the customer, or of the auditor as the lead or a team member.
+pub fn participant_filter(role: Role, user_id: ObjectId) -> Document {
+    match role {
+        Role::Customer => doc! {"customer_id": user_id},
+        Role::Auditor => doc! {
+            "$or": [{"auditor_id": user_id}, {"team.user_id": user_id}],
+        },
+    }
+}
+
+impl AuditFilter {
+    pub fn to_document(&self, role: Role, user_id: ObjectId) -> error::Result<Document> {
+        let mut filter = participant_filter(role, user_id);
+
+        if let Some(status) = &self.status {
+            filter.insert("status", to_bson(status)?);
+        }
+
+        if let Some(tag) = &self.tag {
+            filter.insert("tags", tag);
+        }
+
+        filter.extend(TimeRange::overlap_filter(self.from, self.to)?);
+
+        Ok(filter)
+    }
+
+    pub fn sort(&self) -> Document {
+        doc! {"last_modified": self.sort.unwrap_or_default().direction()}
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditReason {
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResolveDispute {
+    pub action: AuditAction,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateReviewRound {
+    pub commit: String,
+    pub scope: Option<Vec<String>>,
+    pub time: TimeRange,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ChangeReviewRound {
+    pub commit: Option<String>,
+    pub scope: Option<Vec<String>>,
+    pub time: Option<TimeRange>,
+    pub verdicts: Option<Vec<IssueVerdict>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -48,43 +123,187 @@ pub struct CreateIssue {
     pub name: String,
     pub description: String,
     pub status: Status,
-    pub severity: String,
+    pub severity: Severity,
+    pub cvss: Option<String>,
     pub category: String,
     #[serde(default)]
     pub links: Vec<String>,
+    #[serde(default)]
+    pub locations: Vec<CodeLocation>,
     pub feedback: Option<String>,
 }
 
 impl CreateIssue {
-    pub fn to_issue(self, id: usize) -> Issue<ObjectId> {
-        Issue {
+    pub fn to_issue(self, id: usize) -> error::Result<Issue<ObjectId>> {
+        let cvss = self.cvss.as_deref().map(Cvss::parse).transpose()?;
+
+        Ok(Issue {
             id,
             name: self.name,
             description: self.description,
-            status: self.status,
+            status: self.status.clone(),
             severity: self.severity,
+            cvss,
             events: Vec::new(),
             category: self.category,
             links: self.links,
+            locations: self.locations,
             include: true,
             feedback: self.feedback.unwrap_or_default(),
+            comments: Vec::new(),
+            assignee: None,
+            due_date: None,
+            remediation_note: None,
+            overdue_notified: false,
+            status_history: vec![IssueTransition::new(self.status)],
             last_modified: Utc::now().timestamp(),
-            read: HashMap::new(),
-        }
+        })
     }
 
-    pub fn to_issue_map(map: Vec<Self>) -> Vec<Issue<ObjectId>> {
+    pub fn to_issue_map(map: Vec<Self>) -> error::Result<Vec<Issue<ObjectId>>> {
         map.into_iter()
             .enumerate()
-            .map(|(idx, issue)| issue.to_issue(idx))
+            .map(|(idx, issue)| issue.to_issue(idx + 1))
             .collect()
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ImportFormat {
+    #[serde(alias = "sarif", alias = "SARIF")]
+    Sarif,
+    #[serde(alias = "slither")]
+    Slither,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportParams {
+    pub format: ImportFormat,
+    /// Repository and commit of reported file paths, for reports that don't name them.
+    pub repo: Option<String>,
+    pub commit: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    #[serde(alias = "sarif", alias = "SARIF")]
+    Sarif,
+    #[serde(alias = "csv", alias = "CSV")]
+    Csv,
+    #[serde(alias = "markdown", alias = "md")]
+    Markdown,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Sarif => "application/sarif+json",
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Markdown => "text/markdown; charset=utf-8",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Sarif => "sarif",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Markdown => "md",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportParams {
+    pub format: ExportFormat,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportSummary {
+    pub findings: usize,
+    pub created: Vec<PublicIssue>,
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateFindingTemplate {
+    pub name: String,
+    pub category: String,
+    pub severity: Severity,
+    pub description: String,
+    #[serde(default)]
+    pub remediation: String,
+    #[serde(default)]
+    pub organization_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ChangeFindingTemplate {
+    pub name: Option<String>,
+    pub category: Option<String>,
+    pub severity: Option<Severity>,
+    pub description: Option<String>,
+    pub remediation: Option<String>,
+}
+
+/// Creates an issue from a finding template, `{{variable}}` placeholders
+/// in the template name, description and remediation are substituted.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CreateIssueFromTemplate {
+    pub template_id: String,
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    pub status: Option<Status>,
+    pub cvss: Option<String>,
+    #[serde(default)]
+    pub links: Vec<String>,
+    #[serde(default)]
+    pub locations: Vec<CodeLocation>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemediationItem {
+    pub issue_id: usize,
+    pub code: String,
+    pub name: String,
+    pub severity: Severity,
+    pub status: Status,
+    pub assignee: Option<String>,
+    pub due_date: Option<i64>,
+    pub remediation_note: Option<String>,
+    pub overdue: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemediationSummary {
+    pub total: usize,
+    pub resolved: usize,
+    pub overdue: usize,
+    pub unassigned: usize,
+    pub without_due_date: usize,
+    pub issues: Vec<RemediationItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Timeline {
+    pub result: Vec<Activity<String>>,
+    #[serde(rename = "totalDocuments")]
+    pub total_documents: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReportVerification {
+    pub audit_id: String,
+    pub project_name: String,
+    pub report_sha256: String,
+    pub matches: bool,
+}
+
 #[derive(Clone, Deserialize, Serialize, Debug)]
 pub struct PublicAudit {
     pub id: String,
     pub auditor_id: String,
+    #[serde(default)]
+    pub team: Vec<PublicTeamMember>,
     pub customer_id: String,
     pub project_id: String,
     #[serde(rename = "isPublic")]
@@ -97,6 +316,8 @@ pub struct PublicAudit {
     pub avatar: String,
     pub description: String,
     pub status: PublicAuditStatus,
+    #[serde(default)]
+    pub status_history: Vec<AuditTransition<String>>,
     pub scope: Vec<String>,
     pub price: i64,
 
@@ -106,20 +327,34 @@ pub struct PublicAudit {
     pub last_modified: i64,
     pub report: Option<String>,
     pub report_name: Option<String>,
+    #[serde(default)]
+    pub report_sha256: Option<String>,
     pub time: TimeRange,
 
     pub issues: Vec<PublicIssue>,
+    #[serde(default)]
+    pub review_rounds: Vec<ReviewRound>,
+    #[serde(default)]
+    pub lines_of_code: Option<u64>,
 
     #[serde(default)]
     pub no_customer: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PublicTeamMember {
+    pub user_id: String,
+    pub role: TeamRole,
+    pub first_name: String,
+    pub last_name: String,
+    pub avatar: String,
+}
+
 impl PublicAudit {
     pub async fn new(
         context: &GeneralContext,
         audit: Audit<ObjectId>,
     ) -> error::Result<PublicAudit> {
-        let auth = context.auth();
         let auditor = context
             .make_request::<PublicAuditor>()
             .get(format!(
@@ -155,6 +390,125 @@ impl PublicAudit {
             ),
         };
 
+        let team_ids = audit.team.iter().map(|member| member.user_id).collect();
+        let team = if audit.team.is_empty() {
+            HashMap::new()
+        } else {
+            request_auditors(context, team_ids, context.server_auth())
+                .await?
+                .into_iter()
+                .map(|auditor| (auditor.user_id().clone(), auditor))
+                .collect()
+        };
+
+        Ok(Self::assemble(
+            &context.auth(),
+            audit,
+            &auditor,
+            &team,
+            project.as_ref(),
+        ))
+    }
+
+    /// Builds the public audits of a list with one request per service
+    /// instead of fetching the auditor and the project of every audit separately.
+    pub async fn new_many(
+        context: &GeneralContext,
+        audits: Vec<Audit<ObjectId>>,
+    ) -> error::Result<Vec<PublicAudit>> {
+        let auth = context.auth();
+
+        let mut auditor_ids = Vec::new();
+        let mut project_ids = Vec::new();
+        let mut customer_ids = Vec::new();
+
+        for audit in &audits {
+            for auditor_id in audit.auditors() {
+                if !auditor_ids.contains(&auditor_id) {
+                    auditor_ids.push(auditor_id);
+                }
+            }
+            if !audit.no_customer {
+                if !project_ids.contains(&audit.project_id) {
+                    project_ids.push(audit.project_id);
+                }
+                if !customer_ids.contains(&audit.customer_id) {
+                    customer_ids.push(audit.customer_id);
+                }
+            }
+        }
+
+        let (auditors, projects, customers) = futures::try_join!(
+            request_auditors(context, auditor_ids, context.server_auth()),
+            request_projects(context, project_ids, context.server_auth()),
+            request_customers(context, customer_ids, context.server_auth()),
+        )?;
+
+        let auditors: HashMap<String, ExtendedAuditor> = auditors
+            .into_iter()
+            .map(|auditor| (auditor.user_id().clone(), auditor))
+            .collect();
+
+        let customers: HashMap<String, PublicCustomer> = customers
+            .into_iter()
+            .map(|customer| (customer.user_id.clone(), customer))
+            .collect();
+
+        let projects: HashMap<String, PublicProject> = projects
+            .into_iter()
+            .map(|mut project| {
+                if let Some(customer) = customers.get(&project.customer_id) {
+                    project.creator_contacts = customer.contacts.clone();
+                }
+                (project.id.clone(), project)
+            })
+            .collect();
+
+        let mut public_audits = Vec::with_capacity(audits.len());
+
+        for audit in audits {
+            let Some(auditor) = auditors.get(&audit.auditor_id.to_hex()) else {
+                return Err(anyhow::anyhow!("Auditor {} not found", audit.auditor_id).code(404));
+            };
+
+            let project = if audit.no_customer {
+                None
+            } else {
+                let Some(project) = projects.get(&audit.project_id.to_hex()) else {
+                    return Err(anyhow::anyhow!("Project {} not found", audit.project_id).code(404));
+                };
+                Some(project)
+            };
+
+            public_audits.push(Self::assemble(&auth, audit, auditor, &auditors, project));
+        }
+
+        Ok(public_audits)
+    }
+
+    fn assemble(
+        auth: &Auth,
+        audit: Audit<ObjectId>,
+        auditor: &ExtendedAuditor,
+        team: &HashMap<String, ExtendedAuditor>,
+        project: Option<&PublicProject>,
+    ) -> PublicAudit {
+        let team = audit
+            .team
+            .iter()
+            .map(|member| {
+                let user_id = member.user_id.to_hex();
+                let profile = team.get(&user_id);
+                PublicTeamMember {
+                    role: member.role,
+                    first_name: profile.map(|p| p.first_name().clone()).unwrap_or_default(),
+                    last_name: profile.map(|p| p.last_name().clone()).unwrap_or_default(),
+                    avatar: profile.map(|p| p.avatar().clone()).unwrap_or_default(),
+                    user_id,
+                }
+            })
+            .collect();
+
         let status = match audit.status {
             AuditStatus::Waiting => PublicAuditStatus::WaitingForAudit,
             AuditStatus::Started => {
@@ -168,10 +522,14 @@ impl PublicAudit {
                     PublicAuditStatus::IssuesWorkflow
                 }
             }
+            AuditStatus::Paused => PublicAuditStatus::Paused,
+            AuditStatus::Review => PublicAuditStatus::Review,
             AuditStatus::Resolved => PublicAuditStatus::Resolved,
+            AuditStatus::Canceled => PublicAuditStatus::Canceled,
+            AuditStatus::Disputed => PublicAuditStatus::Disputed,
         };
 
-        let customer_contacts = if let Some(project) = &project {
+        let customer_contacts = if let Some(project) = project {
             project.creator_contacts.clone()
         } else {
             Contacts {
@@ -181,8 +539,8 @@ impl PublicAudit {
             }
         };
 
-        let project_name = if let Some(project) = &project {
-            if audit.project_name == "" {
+        let project_name = if let Some(project) = project {
+            if audit.project_name.is_empty() {
                 project.name.clone()
             } else {
                 audit.project_name
@@ -191,9 +549,21 @@ impl PublicAudit {
             audit.project_name
         };
 
-        let public_audit = PublicAudit {
+        let is_customer = auth.id() == Some(audit.customer_id) && !audit.no_customer;
+
+        let mut issues: Vec<PublicIssue> = audit
+            .issues
+            .into_iter()
+            .filter(|issue| !(is_customer && issue.status == Status::Draft))
+            .map(|i| auth.public_issue(i))
+            .collect();
+
+        issues.sort_by_key(|issue| issue.severity);
+
+        PublicAudit {
             id: audit.id.to_hex(),
             auditor_id: audit.auditor_id.to_hex(),
+            team,
             customer_id: audit.customer_id.to_hex(),
             project_id: audit.project_id.to_hex(),
             auditor_first_name: auditor.first_name().clone(),
@@ -202,6 +572,7 @@ impl PublicAudit {
             avatar: auditor.avatar().clone(),
             description: audit.description,
             status,
+            status_history: AuditTransition::to_string_map(audit.status_history),
             scope: audit.scope,
             price: audit.price,
             auditor_contacts: auditor.contacts().clone(),
@@ -210,17 +581,14 @@ impl PublicAudit {
             last_modified: audit.last_modified,
             report: audit.report,
             report_name: audit.report_name,
+            report_sha256: audit.report_sha256,
             time: audit.time,
-            issues: audit
-                .issues
-                .into_iter()
-                .map(|i| auth.public_issue(i))
-                .collect(),
+            issues,
+            review_rounds: audit.review_rounds,
+            lines_of_code: audit.lines_of_code,
             public: audit.public,
             no_customer: audit.no_customer,
-        };
-
-        Ok(public_audit)
+        }
     }
 }
 
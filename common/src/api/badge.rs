@@ -50,3 +50,24 @@ pub async fn get_badge(
         .json::<Option<PublicBadge>>()
         .await?)
 }
+
+pub async fn request_badges(
+    context: &GeneralContext,
+    ids: Vec<ObjectId>,
+    auth: Auth,
+) -> error::Result<Vec<PublicBadge>> {
+    Ok(context
+        .make_request::<Vec<ObjectId>>()
+        .post(format!(
+            "{}://{}/{}/badge/data",
+            PROTOCOL.as_str(),
+            AUDITORS_SERVICE.as_str(),
+            API_PREFIX.as_str(),
+        ))
+        .auth(auth)
+        .json(&ids)
+        .send()
+        .await?
+        .json::<Vec<PublicBadge>>()
+        .await?)
+}
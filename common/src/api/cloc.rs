@@ -0,0 +1,112 @@
+use std::{collections::HashMap, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    context::GeneralContext,
+    error,
+    services::{API_PREFIX, CLOC_SERVICE, PROTOCOL},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClocRequest {
+    pub author: String,
+    pub repo: String,
+    pub branch: Option<String>,
+    pub commit: Option<String>,
+}
+
+impl ClocRequest {
+    /// Repository of a GitHub link, e.g. `https://github.com/author/repo/blob/main/src/lib.rs`.
+    pub fn from_link(link: &str) -> Option<Self> {
+        let path = link
+            .trim()
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .trim_start_matches("www.")
+            .strip_prefix("github.com/")?;
+
+        let mut parts = path.split('/').filter(|part| !part.is_empty());
+        let author = parts.next()?.to_string();
+        let repo = parts.next()?.trim_end_matches(".git").to_string();
+
+        let (branch, commit) = match (parts.next(), parts.next()) {
+            (Some("commit"), Some(commit)) => (None, Some(commit.to_string())),
+            (Some("tree" | "blob"), Some(reference)) => (Some(reference.to_string()), None),
+            _ => (None, None),
+        };
+
+        Some(Self {
+            author,
+            repo,
+            branch,
+            commit,
+        })
+    }
+}
+
+// Counting clones the repository, a stuck count must not hold the request forever
+const CLOC_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Deserialize)]
+struct ClocLine {
+    code: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ClocCount {
+    languages: HashMap<String, ClocLine>,
+}
+
+async fn request_count(
+    context: &GeneralContext,
+    service: &str,
+    request: &ClocRequest,
+) -> error::Result<u64> {
+    let count = context
+        .make_request::<ClocRequest>()
+        .post(format!(
+            "{}://{}/{}/cloc/count",
+            PROTOCOL.as_str(),
+            service,
+            API_PREFIX.as_str(),
+        ))
+        .auth(context.server_auth())
+        .json(request)
+        .timeout(CLOC_TIMEOUT)
+        .send()
+        .await?
+        .json::<ClocCount>()
+        .await?;
+
+    Ok(count.languages.values().map(|line| line.code).sum())
+}
+
+/// Lines of code of the repositories in the scope, `None` when none of them could be counted
+/// or the cloc service is not configured.
+pub async fn request_lines_of_code(context: &GeneralContext, scope: &[String]) -> Option<u64> {
+    let service = CLOC_SERVICE.as_deref()?;
+
+    let mut requests: Vec<ClocRequest> = Vec::new();
+    for request in scope.iter().filter_map(|link| ClocRequest::from_link(link)) {
+        if !requests.contains(&request) {
+            requests.push(request);
+        }
+    }
+
+    let mut lines_of_code = None;
+
+    for request in requests {
+        match request_count(context, service, &request).await {
+            Ok(lines) => *lines_of_code.get_or_insert(0) += lines,
+            Err(err) => log::warn!(
+                "Failed to count lines of {}/{}: {}",
+                request.author,
+                request.repo,
+                err
+            ),
+        }
+    }
+
+    lines_of_code
+}
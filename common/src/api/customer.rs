@@ -28,3 +28,28 @@ pub async fn request_customer(
         .json::<PublicCustomer>()
         .await?)
 }
+
+pub async fn request_customers(
+    context: &GeneralContext,
+    ids: Vec<ObjectId>,
+    auth: Auth,
+) -> error::Result<Vec<PublicCustomer>> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(context
+        .make_request::<Vec<ObjectId>>()
+        .post(format!(
+            "{}://{}/{}/customer/data",
+            PROTOCOL.as_str(),
+            CUSTOMERS_SERVICE.as_str(),
+            API_PREFIX.as_str(),
+        ))
+        .auth(auth)
+        .json(&ids)
+        .send()
+        .await?
+        .json::<Vec<PublicCustomer>>()
+        .await?)
+}
@@ -1,27 +1,99 @@
+use mongodb::bson::oid::ObjectId;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     default_timestamp,
-    entities::issue::{Event, Status},
+    entities::{
+        cvss::Cvss,
+        issue::{CodeLocation, Comment, CommentEdit, Event, Severity, Status},
+    },
 };
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct PublicIssue {
     pub id: usize,
+    pub code: String,
     pub name: String,
     pub description: String,
-    pub severity: String,
+    pub severity: Severity,
+    #[serde(default)]
+    pub cvss: Option<Cvss>,
 
     pub category: String,
     #[serde(default)]
     pub links: Vec<String>,
+    #[serde(default)]
+    pub locations: Vec<CodeLocation>,
 
     pub status: Status,
     pub include: bool,
 
     pub feedback: String,
     pub events: Vec<Event<String>>,
+    #[serde(default)]
+    pub comments: Vec<PublicComment>,
+    #[serde(default)]
+    pub unread: usize,
+
+    #[serde(default)]
+    pub assignee: Option<String>,
+    #[serde(default)]
+    pub due_date: Option<i64>,
+    #[serde(default)]
+    pub remediation_note: Option<String>,
+    #[serde(default)]
+    pub overdue: bool,
+
     #[serde(default = "default_timestamp")]
     pub last_modified: i64,
-    pub read: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PublicComment {
+    pub id: String,
+    pub parent: Option<String>,
+    pub user: String,
+    pub text: String,
+    pub timestamp: i64,
+    pub edits: Vec<CommentEdit>,
+    pub deleted: bool,
+    pub mentions: Vec<String>,
+    pub read: bool,
+}
+
+impl PublicComment {
+    pub fn new(comment: Comment<ObjectId>, viewer: Option<ObjectId>) -> PublicComment {
+        let read = viewer.is_none_or(|viewer| comment.is_read_by(&viewer));
+        let comment = comment.to_string();
+
+        // Deleted comments are kept in the thread so replies still have a parent
+        let (text, edits) = if comment.deleted {
+            (String::new(), Vec::new())
+        } else {
+            (comment.text, comment.edits)
+        };
+
+        PublicComment {
+            id: comment.id,
+            parent: comment.parent,
+            user: comment.user,
+            text,
+            timestamp: comment.timestamp,
+            edits,
+            deleted: comment.deleted,
+            mentions: comment.mentions,
+            read,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreateComment {
+    pub text: String,
+    pub parent: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChangeComment {
+    pub text: String,
 }
@@ -1,14 +1,17 @@
 pub mod notifications;
 pub use notifications::*;
+pub mod analytics;
 pub mod auditor;
 pub mod audits;
 pub mod badge;
 pub mod chat;
+pub mod cloc;
 pub mod codes;
 pub mod customer;
 pub mod events;
 pub mod issue;
 pub mod mail;
+pub mod organization;
 pub mod project;
 pub mod report;
 pub mod requests;
@@ -0,0 +1,51 @@
+use mongodb::bson::oid::ObjectId;
+
+use crate::{
+    auth::Auth,
+    context::GeneralContext,
+    entities::organization::PublicOrganization,
+    error::{self, AddCode},
+    services::{API_PREFIX, AUDITORS_SERVICE, PROTOCOL},
+};
+
+pub async fn request_organization(
+    context: &GeneralContext,
+    id: ObjectId,
+    auth: Auth,
+) -> error::Result<PublicOrganization> {
+    context
+        .make_request::<()>()
+        .get(format!(
+            "{}://{}/{}/organization/{}",
+            PROTOCOL.as_str(),
+            AUDITORS_SERVICE.as_str(),
+            API_PREFIX.as_str(),
+            id
+        ))
+        .auth(auth)
+        .send()
+        .await?
+        .json::<PublicOrganization>()
+        .await
+        .map_err(|_| anyhow::anyhow!("Organization {} not found", id).code(404))
+}
+
+/// Organizations the authenticated auditor is a member of.
+pub async fn request_my_organizations(
+    context: &GeneralContext,
+    auth: Auth,
+) -> error::Result<Vec<PublicOrganization>> {
+    Ok(context
+        .make_request::<()>()
+        .get(format!(
+            "{}://{}/{}/my_organization",
+            PROTOCOL.as_str(),
+            AUDITORS_SERVICE.as_str(),
+            API_PREFIX.as_str(),
+        ))
+        .auth(auth)
+        .send()
+        .await?
+        .json::<Vec<PublicOrganization>>()
+        .await?)
+}
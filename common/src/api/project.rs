@@ -28,3 +28,29 @@ pub async fn request_project(
         .json::<PublicProject>()
         .await?)
 }
+
+/// Unlike `request_project`, `creator_contacts` of the returned projects are not filled in.
+pub async fn request_projects(
+    context: &GeneralContext,
+    ids: Vec<ObjectId>,
+    auth: Auth,
+) -> error::Result<Vec<PublicProject>> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(context
+        .make_request::<Vec<ObjectId>>()
+        .post(format!(
+            "{}://{}/{}/project/data",
+            PROTOCOL.as_str(),
+            CUSTOMERS_SERVICE.as_str(),
+            API_PREFIX.as_str(),
+        ))
+        .auth(auth)
+        .json(&ids)
+        .send()
+        .await?
+        .json::<Vec<PublicProject>>()
+        .await?)
+}
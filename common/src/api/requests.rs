@@ -1,20 +1,51 @@
-use mongodb::bson::oid::ObjectId;
+use std::collections::HashMap;
+
+use mongodb::bson::{doc, oid::ObjectId, Document};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     auth::Auth,
     context::GeneralContext,
     entities::{
-        audit_request::{AuditRequest, TimeRange},
-        auditor::PublicAuditor,
+        audit_request::{AuditRequest, Offer, PriceRange, TimeRange},
+        auditor::{ExtendedAuditor, PublicAuditor},
         contacts::Contacts,
+        customer::PublicCustomer,
         project::PublicProject,
         role::Role,
     },
-    error,
+    error::{self, AddCode},
     services::{API_PREFIX, AUDITORS_SERVICE, AUDITS_SERVICE, CUSTOMERS_SERVICE, PROTOCOL},
 };
 
+use super::{
+    auditor::request_auditors,
+    audits::participant_filter,
+    customer::request_customers,
+    project::request_projects,
+    seartch::{SortOrder, MAX_PER_PAGE},
+};
+
+/// Filters of `my_audit_request`, `from` and `to` select requests whose time range overlaps them.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct RequestFilter {
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    pub sort: Option<SortOrder>,
+}
+
+impl RequestFilter {
+    pub fn to_document(&self, role: Role, user_id: ObjectId) -> error::Result<Document> {
+        let mut filter = participant_filter(role, user_id);
+        filter.extend(TimeRange::overlap_filter(self.from, self.to)?);
+        Ok(filter)
+    }
+
+    pub fn sort(&self) -> Document {
+        doc! {"last_modified": self.sort.unwrap_or_default().direction()}
+    }
+}
+
 #[derive(Clone, Deserialize, Serialize, Debug)]
 pub struct PublicRequest {
     pub id: String,
@@ -33,6 +64,17 @@ pub struct PublicRequest {
     pub auditor_contacts: Contacts,
     pub customer_contacts: Contacts,
     pub last_changer: Role,
+    #[serde(default)]
+    pub offers: Vec<Offer<String>>,
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+    #[serde(default)]
+    pub price_range: Option<PriceRange>,
+    /// Set when the price is outside of the auditor's price range.
+    #[serde(default)]
+    pub price_warning: Option<String>,
+    #[serde(default)]
+    pub organization_id: Option<String>,
 }
 
 impl PublicRequest {
@@ -106,55 +148,215 @@ impl PublicRequest {
             .await
             .map_err(|_| anyhow::anyhow!("Auditor {} not found", request.auditor_id))?;
 
-        Ok(PublicRequest {
+        Ok(Self::assemble(
+            request,
+            &project,
+            &ExtendedAuditor::Auditor(auditor),
+        ))
+    }
+
+    /// Builds the public requests of a list with one request per service
+    /// instead of fetching the auditor and the project of every request separately.
+    pub async fn new_many(
+        context: &GeneralContext,
+        requests: Vec<AuditRequest<ObjectId>>,
+    ) -> error::Result<Vec<PublicRequest>> {
+        let mut auditor_ids = Vec::new();
+        let mut project_ids = Vec::new();
+        let mut customer_ids = Vec::new();
+
+        for request in &requests {
+            if !auditor_ids.contains(&request.auditor_id) {
+                auditor_ids.push(request.auditor_id);
+            }
+            if !project_ids.contains(&request.project_id) {
+                project_ids.push(request.project_id);
+            }
+            if !customer_ids.contains(&request.customer_id) {
+                customer_ids.push(request.customer_id);
+            }
+        }
+
+        let (auditors, projects, customers) = futures::try_join!(
+            request_auditors(context, auditor_ids, context.server_auth()),
+            request_projects(context, project_ids, context.server_auth()),
+            request_customers(context, customer_ids, context.server_auth()),
+        )?;
+
+        let auditors: HashMap<String, ExtendedAuditor> = auditors
+            .into_iter()
+            .map(|auditor| (auditor.user_id().clone(), auditor))
+            .collect();
+
+        let customers: HashMap<String, PublicCustomer> = customers
+            .into_iter()
+            .map(|customer| (customer.user_id.clone(), customer))
+            .collect();
+
+        let projects: HashMap<String, PublicProject> = projects
+            .into_iter()
+            .map(|mut project| {
+                if let Some(customer) = customers.get(&project.customer_id) {
+                    project.creator_contacts = customer.contacts.clone();
+                }
+                (project.id.clone(), project)
+            })
+            .collect();
+
+        let mut public_requests = Vec::with_capacity(requests.len());
+
+        for request in requests {
+            let Some(auditor) = auditors.get(&request.auditor_id.to_hex()) else {
+                return Err(anyhow::anyhow!("Auditor {} not found", request.auditor_id).code(404));
+            };
+
+            let Some(project) = projects.get(&request.project_id.to_hex()) else {
+                return Err(anyhow::anyhow!("Project {} not found", request.project_id).code(404));
+            };
+
+            public_requests.push(Self::assemble(request, project, auditor));
+        }
+
+        Ok(public_requests)
+    }
+
+    fn assemble(
+        request: AuditRequest<ObjectId>,
+        project: &PublicProject,
+        auditor: &ExtendedAuditor,
+    ) -> PublicRequest {
+        // Badges have no price range to compare with
+        let price_warning = match auditor {
+            ExtendedAuditor::Auditor(auditor) => {
+                let range = &auditor.price_range;
+                let out_of_range = request.price < range.from || request.price > range.to;
+                if range.to > 0 && out_of_range {
+                    Some(format!(
+                        "Price {} is outside of the auditor's price range {}-{}",
+                        request.price, range.from, range.to
+                    ))
+                } else {
+                    None
+                }
+            }
+            ExtendedAuditor::Badge(_) => None,
+        };
+
+        let project_scope = match request.latest_offer() {
+            Some(offer) if !offer.scope.is_empty() => offer.scope.clone(),
+            _ => project.scope.clone(),
+        };
+
+        PublicRequest {
             id: request.id.to_hex(),
             customer_id: request.customer_id.to_hex(),
             auditor_id: request.auditor_id.to_hex(),
             project_id: request.project_id.to_hex(),
-            auditor_first_name: auditor.first_name,
-            auditor_last_name: auditor.last_name,
+            auditor_first_name: auditor.first_name().clone(),
+            auditor_last_name: auditor.last_name().clone(),
             description: request.description,
             time: request.time,
-            project_name: project.name,
-            avatar: auditor.avatar,
-            project_scope: project.scope,
-            tags: Some(project.tags),
+            project_name: project.name.clone(),
+            avatar: auditor.avatar().clone(),
+            project_scope,
+            tags: Some(project.tags.clone()),
             price: request.price,
-            auditor_contacts: auditor.contacts,
-            customer_contacts: project.creator_contacts,
+            auditor_contacts: auditor.contacts().clone(),
+            customer_contacts: project.creator_contacts.clone(),
             last_changer: request.last_changer,
-        })
+            offers: request.offers.into_iter().map(Offer::stringify).collect(),
+            expires_at: request.expires_at,
+            price_range: request.price_range,
+            price_warning,
+            organization_id: request.organization_id.map(|id| id.to_hex()),
+        }
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MyAuditRequestResult {
+    pub result: Vec<PublicRequest>,
+    #[serde(rename = "totalDocuments")]
+    pub total_documents: u64,
+}
+
+/// Returns all requests of the auditor, reading every page of `my_audit_request`.
 pub async fn get_audit_requests(
     context: &GeneralContext,
     auth: Auth,
 ) -> error::Result<Vec<PublicRequest>> {
-    Ok(context
-        .make_request::<Vec<PublicRequest>>()
-        .get(format!(
-            "{}://{}/{}/my_audit_request/auditor",
-            PROTOCOL.as_str(),
-            AUDITS_SERVICE.as_str(),
-            API_PREFIX.as_str(),
-        ))
-        .auth(auth)
-        .send()
-        .await?
-        .json::<Vec<PublicRequest>>()
-        .await?)
+    let mut requests = Vec::new();
+
+    for page in 1.. {
+        let response = context
+            .make_request::<MyAuditRequestResult>()
+            .get(format!(
+                "{}://{}/{}/my_audit_request/auditor?page={}&per_page={}",
+                PROTOCOL.as_str(),
+                AUDITS_SERVICE.as_str(),
+                API_PREFIX.as_str(),
+                page,
+                MAX_PER_PAGE,
+            ))
+            .auth(auth)
+            .send()
+            .await?
+            .json::<MyAuditRequestResult>()
+            .await?;
+
+        let is_last = response.result.is_empty();
+        requests.extend(response.result);
+
+        if is_last || requests.len() as u64 >= response.total_documents {
+            break;
+        }
+    }
+
+    Ok(requests)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OfferAction {
+    #[serde(alias = "accept")]
+    Accept,
+    #[serde(alias = "reject")]
+    Reject,
+    #[serde(alias = "counter")]
+    Counter,
+}
+
+/// Answer to the latest offer, fields a counter-offer leaves out are taken from it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RequestOffer {
+    pub action: OfferAction,
+    pub price: Option<i64>,
+    pub time: Option<TimeRange>,
+    pub scope: Option<Vec<String>>,
+    #[serde(default)]
+    pub message: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateRequest {
     pub customer_id: String,
+    /// May be left empty when the request is sent to an organization.
+    #[serde(default)]
     pub auditor_id: String,
     pub project_id: String,
 
     pub price: i64,
     pub description: String,
     pub time: TimeRange,
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+    #[serde(default)]
+    pub organization_id: Option<String>,
+}
+
+/// Auditor of the organization who takes over a request sent to it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AssignRequest {
+    pub auditor_id: String,
 }
 
 pub async fn create_request(
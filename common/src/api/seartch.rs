@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     context::GeneralContext,
-    error,
+    error::{self, AddCode},
     services::{API_PREFIX, PROTOCOL, SEARCH_SERVICE},
 };
 
@@ -23,8 +23,53 @@ pub async fn delete_from_search(context: &GeneralContext, id: ObjectId) -> error
     Ok(())
 }
 
+pub const MAX_PER_PAGE: i32 = 1000;
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct PaginationParams {
     pub page: Option<i32>,
     pub per_page: Option<i32>,
 }
+
+impl PaginationParams {
+    /// Returns `skip` and `limit` of the requested page, pages are numbered from 1.
+    pub fn skip_limit(&self, default_per_page: i32) -> error::Result<(i32, i32)> {
+        let page = self.page.unwrap_or(1);
+        let per_page = self.per_page.unwrap_or(default_per_page);
+
+        if page < 1 {
+            return Err(anyhow::anyhow!("Page must be at least 1").code(400));
+        }
+
+        if !(1..=MAX_PER_PAGE).contains(&per_page) {
+            return Err(anyhow::anyhow!(
+                "Number of items per page must be between 1 and {}",
+                MAX_PER_PAGE
+            )
+            .code(400));
+        }
+
+        let Some(skip) = (page - 1).checked_mul(per_page) else {
+            return Err(anyhow::anyhow!("Page is out of range").code(400));
+        };
+
+        Ok((skip, per_page))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortOrder {
+    pub fn direction(self) -> i32 {
+        match self {
+            SortOrder::Asc => 1,
+            SortOrder::Desc => -1,
+        }
+    }
+}
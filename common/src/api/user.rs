@@ -80,6 +80,31 @@ pub async fn get_by_id(
         .await?)
 }
 
+pub async fn request_users(
+    context: &GeneralContext,
+    ids: Vec<ObjectId>,
+    auth: Auth,
+) -> error::Result<Vec<PublicUser>> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(context
+        .make_request::<Vec<ObjectId>>()
+        .post(format!(
+            "{}://{}/{}/user/data",
+            PROTOCOL.as_str(),
+            USERS_SERVICE.as_str(),
+            API_PREFIX.as_str(),
+        ))
+        .auth(auth)
+        .json(&ids)
+        .send()
+        .await?
+        .json::<Vec<PublicUser>>()
+        .await?)
+}
+
 pub async fn get_by_email(
     context: &GeneralContext,
     email: String,
@@ -7,14 +7,15 @@ use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    api::issue::PublicIssue,
+    api::issue::{PublicComment, PublicIssue},
     constants::DURATION,
     entities::{
         auditor::{Auditor, PublicAuditor},
         badge::{Badge, PublicBadge},
         contacts::Contacts,
         customer::{Customer, PublicCustomer},
-        issue::{Event, Issue},
+        issue::{issue_code, Event, Issue},
+        organization::{Organization, PublicOrganization},
         project::{Project, PublicProject},
     },
     error::{self, AddCode},
@@ -170,6 +171,38 @@ impl Auth {
         }
     }
 
+    pub fn public_organization(&self, organization: Organization<ObjectId>) -> PublicOrganization {
+        let is_member = self.id().is_some_and(|id| organization.is_member(&id));
+        let organization = organization.stringify();
+
+        let mut contacts = Contacts {
+            telegram: None,
+            email: None,
+            public_contacts: false,
+        };
+
+        if organization.contacts.public_contacts || self.full_access() || is_member {
+            contacts = organization.contacts;
+        }
+
+        if &Auth::None == self || &Auth::Service(Service::Search, false) == self {
+            contacts.telegram = None;
+            contacts.email = None;
+        }
+
+        PublicOrganization {
+            id: organization.id,
+            name: organization.name,
+            avatar: organization.avatar,
+            about: organization.about,
+            contacts,
+            tags: organization.tags,
+            price_range: organization.price_range,
+            members: organization.members,
+            kind: "organization".to_string(),
+        }
+    }
+
     pub fn public_project(&self, project: Project<ObjectId>) -> PublicProject {
         let mut contacts = Contacts {
             telegram: None,
@@ -203,21 +236,46 @@ impl Auth {
     }
 
     pub fn public_issue(&self, issue: Issue<ObjectId>) -> PublicIssue {
-        let id = self.id().unwrap();
+        let id = self.id();
+
+        let unread = match id {
+            Some(id) => issue
+                .comments
+                .iter()
+                .filter(|comment| !comment.deleted && !comment.is_read_by(&id))
+                .count(),
+            None => 0,
+        };
+
+        let overdue = issue.is_overdue(Utc::now().timestamp());
+
+        let comments = issue
+            .comments
+            .into_iter()
+            .map(|comment| PublicComment::new(comment, id))
+            .collect();
 
         PublicIssue {
             id: issue.id,
+            code: issue_code(issue.id),
             name: issue.name,
             description: issue.description,
             status: issue.status,
             severity: issue.severity,
+            cvss: issue.cvss,
             category: issue.category,
             links: issue.links,
+            locations: issue.locations,
             include: issue.include,
             feedback: issue.feedback,
             events: Event::to_string_map(issue.events),
+            comments,
+            unread,
+            assignee: issue.assignee,
+            due_date: issue.due_date,
+            remediation_note: issue.remediation_note,
+            overdue,
             last_modified: issue.last_modified,
-            read: *issue.read.get(&id.to_hex()).unwrap_or(&0),
         }
     }
 }
@@ -279,7 +337,7 @@ impl Auth {
             Auth::Service(name, user_auth) => Claims {
                 role: Role::Service,
                 user_id: None,
-                service_name: Some(name.clone()),
+                service_name: Some(*name),
                 exp,
                 user_authorized: Some(*user_auth),
             },
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use serde::Serialize;
 use type_map::concurrent::TypeMap;
@@ -41,7 +41,7 @@ pub struct EffectfullContext(pub Arc<ServiceState>, pub HandlerContext);
 
 impl EffectfullContext {
     pub fn server_auth(&self) -> Auth {
-        self.0.service_auth.clone()
+        self.0.service_auth
     }
 
     pub fn get_repository<T: 'static>(&self) -> Option<RepositoryObject<T>> {
@@ -70,8 +70,8 @@ impl EffectfullContext {
         &self.1.user_auth
     }
 
-    pub fn make_request<T: Serialize>(&self) -> ServiceRequest<T> {
-        ServiceRequest::<T>::new(&self.0.client, self.0.service_auth.clone())
+    pub fn make_request<T: Serialize>(&self) -> ServiceRequest<'_, '_, T> {
+        ServiceRequest::<T>::new(&self.0.client, self.0.service_auth)
     }
 }
 
@@ -81,6 +81,7 @@ pub struct ServiceRequest<'a, 'b, T = ()> {
     url: Option<String>,
     body: Option<&'b T>,
     auth: Auth,
+    timeout: Option<Duration>,
 }
 
 impl<'a, 'b, T: Serialize> ServiceRequest<'a, 'b, T> {
@@ -91,6 +92,7 @@ impl<'a, 'b, T: Serialize> ServiceRequest<'a, 'b, T> {
             method: reqwest::Method::GET,
             url: None,
             body: None,
+            timeout: None,
         }
     }
 
@@ -131,6 +133,9 @@ impl<'a, 'b, T: Serialize> ServiceRequest<'a, 'b, T> {
         if let Some(body) = self.body {
             request = request.json(body);
         }
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
         let response = request.send().await?;
         Ok(response)
     }
@@ -139,4 +144,9 @@ impl<'a, 'b, T: Serialize> ServiceRequest<'a, 'b, T> {
         self.auth = auth;
         self
     }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
 }
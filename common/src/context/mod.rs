@@ -123,7 +123,7 @@ impl GeneralContext {
         }
     }
 
-    pub fn make_request<T: Serialize>(&self) -> ServiceRequest<T> {
+    pub fn make_request<T: Serialize>(&self) -> ServiceRequest<'_, '_, T> {
         match self {
             GeneralContext::Effectfull(context) => {
                 ServiceRequest::<T>::new(&context.0.client, context.0.service_auth)
@@ -0,0 +1,62 @@
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use crate::repository::Entity;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityKind {
+    RequestCreated,
+    RequestChanged,
+    RequestDeleted,
+    AuditCreated,
+    AuditChanged,
+    AuditDeleted,
+    StatusChanged,
+    IssueCreated,
+    IssueChanged,
+    IssueDeleted,
+    IssuesImported,
+    IssuesDisclosed,
+    CommentAdded,
+    CommentChanged,
+    CommentDeleted,
+    ReviewRoundCreated,
+    ReviewRoundChanged,
+}
+
+/// Entry of the append-only activity log. Requests keep their id when they
+/// become audits, so `audit_id` links the negotiation with the audit itself.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Activity<Id> {
+    pub id: Id,
+    pub audit_id: Id,
+    pub user: Option<Id>,
+    pub kind: ActivityKind,
+    pub message: String,
+    pub issue_id: Option<usize>,
+    /// Activity on draft issues is shown to the auditor only
+    #[serde(default)]
+    pub auditor_only: bool,
+    pub timestamp: i64,
+}
+
+impl Activity<ObjectId> {
+    pub fn stringify(self) -> Activity<String> {
+        Activity {
+            id: self.id.to_hex(),
+            audit_id: self.audit_id.to_hex(),
+            user: self.user.map(|user| user.to_hex()),
+            kind: self.kind,
+            message: self.message,
+            issue_id: self.issue_id,
+            auditor_only: self.auditor_only,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+impl Entity for Activity<ObjectId> {
+    fn id(&self) -> ObjectId {
+        self.id
+    }
+}
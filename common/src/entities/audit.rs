@@ -2,15 +2,17 @@ use std::hash::Hash;
 
 use mongodb::bson::oid::ObjectId;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::{
     api::report::PublicReport,
     context::GeneralContext,
+    error::{self, AddCode},
     repository::Entity,
-    services::{API_PREFIX, PROTOCOL, REPORT_SERVICE},
+    services::{API_PREFIX, FILES_SERVICE, PROTOCOL, REPORT_SERVICE},
 };
 
-use super::{audit_request::TimeRange, issue::Issue};
+use super::{audit_request::TimeRange, issue::Issue, review_round::ReviewRound};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum PublicAuditStatus {
@@ -24,13 +26,232 @@ pub enum PublicAuditStatus {
     ReadyForResolve,
     #[serde(rename = "Resolved", alias = "Resolved")]
     Resolved,
+    #[serde(rename = "Paused", alias = "Paused")]
+    Paused,
+    #[serde(rename = "In review", alias = "Review")]
+    Review,
+    #[serde(rename = "Canceled", alias = "Canceled")]
+    Canceled,
+    #[serde(rename = "Disputed", alias = "Disputed")]
+    Disputed,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum AuditStatus {
     Waiting,
     Started,
+    Paused,
+    Review,
     Resolved,
+    Canceled,
+    Disputed,
+}
+
+impl AuditStatus {
+    pub fn apply(&self, action: &AuditAction) -> Option<AuditStatus> {
+        match (self, action) {
+            (AuditStatus::Waiting, AuditAction::Start) => Some(AuditStatus::Started),
+            (AuditStatus::Waiting, AuditAction::Cancel) => Some(AuditStatus::Canceled),
+            (AuditStatus::Started, AuditAction::Pause) => Some(AuditStatus::Paused),
+            (AuditStatus::Started, AuditAction::SubmitForReview) => Some(AuditStatus::Review),
+            (AuditStatus::Started, AuditAction::Resolve) => Some(AuditStatus::Resolved),
+            (AuditStatus::Started, AuditAction::Cancel) => Some(AuditStatus::Canceled),
+            (AuditStatus::Started, AuditAction::Dispute) => Some(AuditStatus::Disputed),
+            (AuditStatus::Paused, AuditAction::Start) => Some(AuditStatus::Started),
+            (AuditStatus::Paused, AuditAction::Cancel) => Some(AuditStatus::Canceled),
+            (AuditStatus::Paused, AuditAction::Dispute) => Some(AuditStatus::Disputed),
+            (AuditStatus::Review, AuditAction::RequestFixes) => Some(AuditStatus::Started),
+            (AuditStatus::Review, AuditAction::ReAudit) => Some(AuditStatus::Started),
+            (AuditStatus::Review, AuditAction::Resolve) => Some(AuditStatus::Resolved),
+            (AuditStatus::Review, AuditAction::Cancel) => Some(AuditStatus::Canceled),
+            (AuditStatus::Review, AuditAction::Dispute) => Some(AuditStatus::Disputed),
+            (AuditStatus::Disputed, AuditAction::Start) => Some(AuditStatus::Started),
+            (AuditStatus::Disputed, AuditAction::Resolve) => Some(AuditStatus::Resolved),
+            (AuditStatus::Disputed, AuditAction::Cancel) => Some(AuditStatus::Canceled),
+            _ => None,
+        }
+    }
+
+    pub fn is_final(&self) -> bool {
+        matches!(self, AuditStatus::Resolved | AuditStatus::Canceled)
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        matches!(self, AuditStatus::Disputed | AuditStatus::Canceled)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum AuditAction {
+    #[serde(alias = "start")]
+    Start,
+    #[serde(alias = "pause")]
+    Pause,
+    #[serde(alias = "submit_for_review", alias = "submitForReview")]
+    SubmitForReview,
+    #[serde(alias = "request_fixes", alias = "requestFixes")]
+    RequestFixes,
+    #[serde(alias = "reaudit", alias = "re_audit", alias = "reAudit")]
+    ReAudit,
+    #[serde(alias = "resolve")]
+    Resolve,
+    #[serde(alias = "cancel")]
+    Cancel,
+    #[serde(alias = "dispute")]
+    Dispute,
+}
+
+impl AuditAction {
+    pub fn is_customer(&self) -> bool {
+        match self {
+            AuditAction::RequestFixes
+            | AuditAction::Resolve
+            | AuditAction::Cancel
+            | AuditAction::Dispute => true,
+            AuditAction::Start
+            | AuditAction::Pause
+            | AuditAction::SubmitForReview
+            | AuditAction::ReAudit => false,
+        }
+    }
+
+    pub fn is_auditor(&self) -> bool {
+        match self {
+            AuditAction::Start
+            | AuditAction::Pause
+            | AuditAction::SubmitForReview
+            | AuditAction::ReAudit
+            | AuditAction::Resolve
+            | AuditAction::Cancel
+            | AuditAction::Dispute => true,
+            AuditAction::RequestFixes => false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AuditTransition<Id> {
+    pub from: AuditStatus,
+    pub to: AuditStatus,
+    pub action: AuditAction,
+    pub user: Option<Id>,
+    /// In microseconds, like the other audit times.
+    pub timestamp: i64,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl AuditTransition<String> {
+    pub fn parse(self) -> AuditTransition<ObjectId> {
+        AuditTransition {
+            from: self.from,
+            to: self.to,
+            action: self.action,
+            user: self.user.map(|user| user.parse().unwrap()),
+            timestamp: self.timestamp,
+            reason: self.reason,
+        }
+    }
+
+    pub fn parse_map(map: Vec<Self>) -> Vec<AuditTransition<ObjectId>> {
+        map.into_iter().map(|v| v.parse()).collect()
+    }
+}
+
+impl AuditTransition<ObjectId> {
+    pub fn to_string(self) -> AuditTransition<String> {
+        AuditTransition {
+            from: self.from,
+            to: self.to,
+            action: self.action,
+            user: self.user.map(|user| user.to_hex()),
+            timestamp: self.timestamp,
+            reason: self.reason,
+        }
+    }
+
+    pub fn to_string_map(map: Vec<Self>) -> Vec<AuditTransition<String>> {
+        map.into_iter().map(|v| v.to_string()).collect()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ReminderKind {
+    AboutToStart,
+    AboutToFinish,
+    Submitted,
+}
+
+/// Reminder that was already sent. `at` is the start, the deadline or the submission
+/// it reminds of, in milliseconds, so a rescheduled or resubmitted audit gets a new one.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct SentReminder {
+    pub kind: ReminderKind,
+    pub at: i64,
+}
+
+/// What a team member can do besides reading the audit.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+pub enum TeamRole {
+    /// Works on issues and the audit status, like the lead auditor.
+    Auditor,
+    /// Only reads the audit.
+    Reviewer,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct TeamMember<Id> {
+    pub user_id: Id,
+    pub role: TeamRole,
+}
+
+impl TeamMember<String> {
+    pub fn parse(self) -> error::Result<TeamMember<ObjectId>> {
+        Ok(TeamMember {
+            user_id: self.user_id.parse()?,
+            role: self.role,
+        })
+    }
+}
+
+impl TeamMember<ObjectId> {
+    pub fn stringify(self) -> TeamMember<String> {
+        TeamMember {
+            user_id: self.user_id.to_hex(),
+            role: self.role,
+        }
+    }
+
+    /// Parses a new team of the lead auditor, which must not include the lead,
+    /// the customer or the same user twice.
+    pub fn parse_team(
+        team: Vec<TeamMember<String>>,
+        lead: ObjectId,
+        customer: ObjectId,
+    ) -> error::Result<Vec<Self>> {
+        let mut members: Vec<Self> = Vec::with_capacity(team.len());
+
+        for member in team {
+            let member = member.parse()?;
+
+            if member.user_id == lead || member.user_id == customer {
+                return Err(anyhow::anyhow!(
+                    "The lead auditor and the customer can't be team members"
+                )
+                .code(400));
+            }
+
+            if members.iter().any(|other| other.user_id == member.user_id) {
+                return Err(
+                    anyhow::anyhow!("User {} is in the team twice", member.user_id).code(400),
+                );
+            }
+
+            members.push(member);
+        }
+
+        Ok(members)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -38,7 +259,10 @@ pub struct Audit<Id: Eq + Hash> {
     #[serde(rename = "_id")]
     pub id: Id,
     pub customer_id: Id,
+    /// Lead auditor, the rest of the team is in `team`.
     pub auditor_id: Id,
+    #[serde(default)]
+    pub team: Vec<TeamMember<Id>>,
     pub project_id: Id,
     #[serde(default)]
     pub public: bool,
@@ -47,18 +271,35 @@ pub struct Audit<Id: Eq + Hash> {
     pub project_name: String,
     pub description: String,
     pub status: AuditStatus,
+    #[serde(default)]
+    pub status_history: Vec<AuditTransition<Id>>,
     pub scope: Vec<String>,
     #[serde(default)]
     pub tags: Vec<String>,
     pub price: i64,
+    /// Lines of code in scope, counted by the cloc service when the audit is started
+    #[serde(default)]
+    pub lines_of_code: Option<u64>,
 
     pub last_modified: i64,
     pub report: Option<String>,
     pub report_name: Option<String>,
+    /// SHA-256 of the report PDF, taken when the audit is resolved
+    #[serde(default)]
+    pub report_sha256: Option<String>,
     pub time: TimeRange,
 
     #[serde(default)]
     pub issues: Vec<Issue<Id>>,
+    #[serde(default)]
+    pub issue_counter: usize,
+    /// Last id given to an issue event, event ids are unique within the audit
+    #[serde(default)]
+    pub event_counter: usize,
+    #[serde(default)]
+    pub review_rounds: Vec<ReviewRound>,
+    #[serde(default)]
+    pub reminders: Vec<SentReminder>,
 
     #[serde(default)]
     pub no_customer: bool,
@@ -70,47 +311,93 @@ impl Audit<String> {
             id: self.id.parse().unwrap(),
             customer_id: self.customer_id.parse().unwrap(),
             auditor_id: self.auditor_id.parse().unwrap(),
+            team: self
+                .team
+                .into_iter()
+                .map(|member| member.parse().unwrap())
+                .collect(),
             project_id: self.project_id.parse().unwrap(),
             project_name: self.project_name,
             description: self.description,
             status: self.status,
+            status_history: AuditTransition::parse_map(self.status_history),
             scope: self.scope,
             tags: self.tags,
             price: self.price,
             last_modified: self.last_modified,
             report: self.report,
             report_name: self.report_name,
+            report_sha256: self.report_sha256,
             time: self.time,
             issues: Issue::parse_map(self.issues),
+            issue_counter: self.issue_counter,
+            event_counter: self.event_counter,
+            review_rounds: self.review_rounds,
+            reminders: self.reminders,
+            lines_of_code: self.lines_of_code,
             public: self.public,
             no_customer: self.no_customer,
         }
     }
 }
 
+/// Hex encoded SHA-256 of a report file, as stored in `Audit::report_sha256`
+pub fn report_digest(report: &[u8]) -> String {
+    sha256::digest(report)
+}
+
 impl Audit<ObjectId> {
     pub fn stringify(self) -> Audit<String> {
         Audit {
             id: self.id.to_hex(),
             customer_id: self.customer_id.to_hex(),
             auditor_id: self.auditor_id.to_hex(),
+            team: self.team.into_iter().map(TeamMember::stringify).collect(),
             project_id: self.project_id.to_hex(),
             project_name: self.project_name,
             description: self.description,
             status: self.status,
+            status_history: AuditTransition::to_string_map(self.status_history),
             scope: self.scope,
             tags: self.tags,
             price: self.price,
             last_modified: self.last_modified,
             report: self.report,
             report_name: self.report_name,
+            report_sha256: self.report_sha256,
             time: self.time,
             issues: Issue::to_string_map(self.issues),
+            issue_counter: self.issue_counter,
+            event_counter: self.event_counter,
+            review_rounds: self.review_rounds,
+            reminders: self.reminders,
+            lines_of_code: self.lines_of_code,
             public: self.public,
             no_customer: self.no_customer,
         }
     }
 
+    /// The lead auditor or any team member.
+    pub fn is_auditor(&self, user_id: ObjectId) -> bool {
+        self.auditor_id == user_id || self.team.iter().any(|member| member.user_id == user_id)
+    }
+
+    /// The lead auditor or a team member with the `Auditor` role.
+    pub fn is_editor(&self, user_id: ObjectId) -> bool {
+        self.auditor_id == user_id
+            || self
+                .team
+                .iter()
+                .any(|member| member.user_id == user_id && member.role == TeamRole::Auditor)
+    }
+
+    /// The lead auditor first, then the team.
+    pub fn auditors(&self) -> Vec<ObjectId> {
+        let mut auditors = vec![self.auditor_id];
+        auditors.extend(self.team.iter().map(|member| member.user_id));
+        auditors
+    }
+
     pub async fn resolve(&mut self, context: &GeneralContext) {
         if self.report.is_none() {
             let public_report = context
@@ -137,6 +424,48 @@ impl Audit<ObjectId> {
             self.report = Some(public_report.path.clone());
             self.report_name = Some(public_report.path);
         }
+
+        self.hash_report(context).await;
+    }
+
+    pub async fn hash_report(&mut self, context: &GeneralContext) {
+        let Some(report) = &self.report else {
+            return;
+        };
+
+        let response = context
+            .make_request::<()>()
+            .get(format!(
+                "{}://{}/{}/file/{}",
+                PROTOCOL.as_str(),
+                FILES_SERVICE.as_str(),
+                API_PREFIX.as_str(),
+                report
+            ))
+            .auth(context.server_auth())
+            .send()
+            .await;
+
+        let report = match response {
+            Ok(response) if response.status().is_success() => response.bytes().await,
+            Ok(response) => {
+                log::warn!(
+                    "Failed to fetch report {}: status {}",
+                    report,
+                    response.status()
+                );
+                return;
+            }
+            Err(err) => {
+                log::warn!("Failed to fetch report {}: {}", report, err);
+                return;
+            }
+        };
+
+        match report {
+            Ok(report) => self.report_sha256 = Some(report_digest(&report)),
+            Err(err) => log::warn!("Failed to read report {}: {}", self.id, err),
+        }
     }
 }
 
@@ -1,10 +1,13 @@
-use mongodb::bson::oid::ObjectId;
+use mongodb::bson::{doc, oid::ObjectId, Document};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use crate::repository::Entity;
+use crate::{
+    error::{self, AddCode},
+    repository::Entity,
+};
 
-use super::role::Role;
+use super::{audit::TeamMember, role::Role};
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, PartialEq, Clone, Default)]
 pub struct PriceRange {
@@ -12,12 +15,86 @@ pub struct PriceRange {
     pub to: i64,
 }
 
+/// In milliseconds, as sent by the frontend.
 #[derive(Debug, Serialize, Deserialize, ToSchema, PartialEq, Clone)]
 pub struct TimeRange {
     pub from: i64,
     pub to: i64,
 }
 
+impl TimeRange {
+    /// Filter on the `time` field matching ranges that overlap `[from, to]`.
+    pub fn overlap_filter(from: Option<i64>, to: Option<i64>) -> error::Result<Document> {
+        if let (Some(from), Some(to)) = (from, to) {
+            if from > to {
+                return Err(anyhow::anyhow!("Start of the date range is after its end").code(400));
+            }
+        }
+
+        let mut filter = Document::new();
+        if let Some(from) = from {
+            filter.insert("time.to", doc! {"$gte": from});
+        }
+        if let Some(to) = to {
+            filter.insert("time.from", doc! {"$lte": to});
+        }
+
+        Ok(filter)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+pub enum OfferStatus {
+    Pending,
+    Accepted,
+    Rejected,
+    Countered,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct Offer<Id> {
+    pub author: Id,
+    pub role: Role,
+    pub price: i64,
+    pub time: TimeRange,
+    #[serde(default)]
+    pub scope: Vec<String>,
+    #[serde(default)]
+    pub message: String,
+    pub status: OfferStatus,
+    pub timestamp: i64,
+}
+
+impl Offer<String> {
+    pub fn parse(self) -> Offer<ObjectId> {
+        Offer {
+            author: self.author.parse().unwrap(),
+            role: self.role,
+            price: self.price,
+            time: self.time,
+            scope: self.scope,
+            message: self.message,
+            status: self.status,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+impl Offer<ObjectId> {
+    pub fn stringify(self) -> Offer<String> {
+        Offer {
+            author: self.author.to_hex(),
+            role: self.role,
+            price: self.price,
+            time: self.time,
+            scope: self.scope,
+            message: self.message,
+            status: self.status,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
 pub struct AuditRequest<Id> {
     pub id: Id,
@@ -31,6 +108,23 @@ pub struct AuditRequest<Id> {
     pub last_modified: i64,
     pub last_changer: Role,
     pub time: TimeRange,
+
+    /// Negotiation history, oldest first. `price`, `time` and `last_changer`
+    /// always mirror the latest offer.
+    #[serde(default)]
+    pub offers: Vec<Offer<Id>>,
+    /// In milliseconds, like `time`.
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+    /// Budget of the customer.
+    #[serde(default)]
+    pub price_range: Option<PriceRange>,
+    /// Auditors the lead brings to the audit.
+    #[serde(default)]
+    pub team: Vec<TeamMember<Id>>,
+    /// Set when the request was sent to an organization, its admins assign the auditor.
+    #[serde(default)]
+    pub organization_id: Option<Id>,
 }
 
 impl AuditRequest<String> {
@@ -45,6 +139,15 @@ impl AuditRequest<String> {
             last_modified: self.last_modified,
             last_changer: self.last_changer,
             time: self.time,
+            offers: self.offers.into_iter().map(Offer::parse).collect(),
+            expires_at: self.expires_at,
+            price_range: self.price_range,
+            team: self
+                .team
+                .into_iter()
+                .map(|member| member.parse().unwrap())
+                .collect(),
+            organization_id: self.organization_id.map(|id| id.parse().unwrap()),
         }
     }
 }
@@ -61,7 +164,32 @@ impl AuditRequest<ObjectId> {
             last_modified: self.last_modified,
             last_changer: self.last_changer,
             time: self.time,
+            offers: self.offers.into_iter().map(Offer::stringify).collect(),
+            expires_at: self.expires_at,
+            price_range: self.price_range,
+            team: self.team.into_iter().map(TeamMember::stringify).collect(),
+            organization_id: self.organization_id.map(|id| id.to_hex()),
+        }
+    }
+}
+
+impl<Id> AuditRequest<Id> {
+    pub fn latest_offer(&self) -> Option<&Offer<Id>> {
+        self.offers.last()
+    }
+
+    /// Appends an offer, a pending offer it answers becomes countered.
+    pub fn push_offer(&mut self, offer: Offer<Id>) {
+        if let Some(latest) = self.offers.last_mut() {
+            if latest.status == OfferStatus::Pending {
+                latest.status = OfferStatus::Countered;
+            }
         }
+
+        self.price = offer.price;
+        self.time = offer.time.clone();
+        self.last_changer = offer.role;
+        self.offers.push(offer);
     }
 }
 
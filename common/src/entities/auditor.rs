@@ -98,6 +98,13 @@ pub enum ExtendedAuditor {
 }
 
 impl ExtendedAuditor {
+    pub fn user_id(&self) -> &String {
+        match self {
+            ExtendedAuditor::Auditor(auditor) => &auditor.user_id,
+            ExtendedAuditor::Badge(badge) => &badge.user_id,
+        }
+    }
+
     pub fn avatar(&self) -> &String {
         match self {
             ExtendedAuditor::Auditor(auditor) => &auditor.avatar,
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+const CVSS_PREFIX: &str = "CVSS:3.1";
+
+const BASE_METRICS: [&str; 8] = ["AV", "AC", "PR", "UI", "S", "C", "I", "A"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Cvss {
+    pub vector: String,
+    pub score: f64,
+}
+
+impl Cvss {
+    pub fn parse(vector: &str) -> anyhow::Result<Cvss> {
+        let vector = vector.trim();
+
+        let mut parts = vector.split('/');
+        if parts.next() != Some(CVSS_PREFIX) {
+            anyhow::bail!("CVSS vector must start with {}", CVSS_PREFIX);
+        }
+
+        let mut metrics = HashMap::new();
+        for part in parts {
+            let Some((metric, value)) = part.split_once(':') else {
+                anyhow::bail!("Invalid CVSS metric {}", part);
+            };
+
+            if !BASE_METRICS.contains(&metric) {
+                anyhow::bail!("Unsupported CVSS metric {}", metric);
+            }
+
+            if metrics.insert(metric, value).is_some() {
+                anyhow::bail!("Duplicated CVSS metric {}", metric);
+            }
+        }
+
+        let metric = |name: &str| {
+            metrics
+                .get(name)
+                .copied()
+                .ok_or_else(|| anyhow::anyhow!("Missing CVSS metric {}", name))
+        };
+
+        let invalid =
+            |name: &str, value: &str| anyhow::anyhow!("Invalid CVSS value {}:{}", name, value);
+
+        let scope_changed = match metric("S")? {
+            "U" => false,
+            "C" => true,
+            value => return Err(invalid("S", value)),
+        };
+
+        let attack_vector = match metric("AV")? {
+            "N" => 0.85,
+            "A" => 0.62,
+            "L" => 0.55,
+            "P" => 0.2,
+            value => return Err(invalid("AV", value)),
+        };
+
+        let attack_complexity = match metric("AC")? {
+            "L" => 0.77,
+            "H" => 0.44,
+            value => return Err(invalid("AC", value)),
+        };
+
+        let privileges_required = match (metric("PR")?, scope_changed) {
+            ("N", _) => 0.85,
+            ("L", false) => 0.62,
+            ("L", true) => 0.68,
+            ("H", false) => 0.27,
+            ("H", true) => 0.5,
+            (value, _) => return Err(invalid("PR", value)),
+        };
+
+        let user_interaction = match metric("UI")? {
+            "N" => 0.85,
+            "R" => 0.62,
+            value => return Err(invalid("UI", value)),
+        };
+
+        let impact_metric = |name: &str| match metric(name)? {
+            "H" => Ok(0.56),
+            "L" => Ok(0.22),
+            "N" => Ok(0.0),
+            value => Err(invalid(name, value)),
+        };
+
+        let impact_sub_score = 1.0
+            - (1.0 - impact_metric("C")?)
+                * (1.0 - impact_metric("I")?)
+                * (1.0 - impact_metric("A")?);
+
+        let impact = if scope_changed {
+            7.52 * (impact_sub_score - 0.029) - 3.25 * (impact_sub_score - 0.02_f64).powi(15)
+        } else {
+            6.42 * impact_sub_score
+        };
+
+        let exploitability =
+            8.22 * attack_vector * attack_complexity * privileges_required * user_interaction;
+
+        let score = if impact <= 0.0 {
+            0.0
+        } else if scope_changed {
+            round_up((1.08 * (impact + exploitability)).min(10.0))
+        } else {
+            round_up((impact + exploitability).min(10.0))
+        };
+
+        Ok(Cvss {
+            vector: vector.to_string(),
+            score,
+        })
+    }
+}
+
+// Roundup as defined in appendix A of the CVSS v3.1 specification
+fn round_up(value: f64) -> f64 {
+    let int_input = (value * 100_000.0).round() as i64;
+    if int_input % 10_000 == 0 {
+        int_input as f64 / 100_000.0
+    } else {
+        ((int_input / 10_000) + 1) as f64 / 10.0
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use crate::repository::Entity;
+
+use super::issue::Severity;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FindingTemplate<Id> {
+    pub id: Id,
+    pub auditor_id: Id,
+    pub name: String,
+    pub category: String,
+    pub severity: Severity,
+    pub description: String,
+    #[serde(default)]
+    pub remediation: String,
+    pub last_modified: i64,
+    /// Shares the template with the members of the organization.
+    #[serde(default)]
+    pub organization_id: Option<Id>,
+}
+
+impl FindingTemplate<String> {
+    pub fn parse(self) -> FindingTemplate<ObjectId> {
+        FindingTemplate {
+            id: self.id.parse().unwrap(),
+            auditor_id: self.auditor_id.parse().unwrap(),
+            name: self.name,
+            category: self.category,
+            severity: self.severity,
+            description: self.description,
+            remediation: self.remediation,
+            last_modified: self.last_modified,
+            organization_id: self.organization_id.map(|id| id.parse().unwrap()),
+        }
+    }
+}
+
+impl FindingTemplate<ObjectId> {
+    pub fn stringify(self) -> FindingTemplate<String> {
+        FindingTemplate {
+            id: self.id.to_hex(),
+            auditor_id: self.auditor_id.to_hex(),
+            name: self.name,
+            category: self.category,
+            severity: self.severity,
+            description: self.description,
+            remediation: self.remediation,
+            last_modified: self.last_modified,
+            organization_id: self.organization_id.map(|id| id.to_hex()),
+        }
+    }
+}
+
+impl<Id> FindingTemplate<Id> {
+    /// Description of the issue created from the template, remediation goes into its own section.
+    pub fn issue_description(&self) -> String {
+        if self.remediation.is_empty() {
+            self.description.clone()
+        } else {
+            format!(
+                "{}\n\n### Remediation\n\n{}",
+                self.description, self.remediation
+            )
+        }
+    }
+}
+
+impl Entity for FindingTemplate<ObjectId> {
+    fn id(&self) -> ObjectId {
+        self.id
+    }
+}
+
+/// Replaces `{{variable}}` placeholders with their values.
+/// Fails with the name of the first placeholder that has no value.
+pub fn substitute(text: &str, variables: &HashMap<String, String>) -> Result<String, String> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+
+        let name = rest[start + 2..start + 2 + end].trim();
+        let Some(value) = variables.get(name) else {
+            return Err(name.to_string());
+        };
+
+        result.push_str(&rest[..start]);
+        result.push_str(value);
+        rest = &rest[start + 2 + end + 2..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
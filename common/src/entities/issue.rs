@@ -1,11 +1,12 @@
 use std::{collections::HashMap, hash::Hash};
 
+use chrono::Utc;
 use mongodb::bson::oid::ObjectId;
 use serde::{Deserialize, Serialize};
 
 use crate::{access_rules::AccessRules, auth::Auth};
 
-use super::audit::Audit;
+use super::{audit::Audit, cvss::Cvss};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub enum Status {
@@ -85,13 +86,142 @@ impl Action {
 
 use crate::default_timestamp;
 
-pub fn severity_to_integer(severity: &str) -> usize {
-    match severity {
-        "Critical" | "critical" => 0,
-        "Major" | "major" => 1,
-        "Medium" | "medium" => 2,
-        "Minor" | "minor" => 3,
-        _ => 4,
+pub const ISSUE_CODE_PREFIX: &str = "AUD";
+
+pub fn issue_code(id: usize) -> String {
+    format!("{}-{}", ISSUE_CODE_PREFIX, id)
+}
+
+pub fn parse_issue_code(code: &str) -> Option<usize> {
+    let id = match code.split_once('-') {
+        Some((prefix, id)) if prefix.eq_ignore_ascii_case(ISSUE_CODE_PREFIX) => id,
+        Some(_) => return None,
+        None => code,
+    };
+    id.parse().ok()
+}
+
+#[derive(
+    Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default,
+)]
+pub enum Severity {
+    #[serde(alias = "critical", alias = "CRITICAL")]
+    Critical,
+    #[serde(alias = "major", alias = "MAJOR", alias = "High", alias = "high")]
+    Major,
+    #[serde(alias = "medium", alias = "MEDIUM")]
+    Medium,
+    #[serde(alias = "minor", alias = "MINOR", alias = "Low", alias = "low")]
+    Minor,
+    #[serde(
+        alias = "informational",
+        alias = "Info",
+        alias = "info",
+        alias = "None"
+    )]
+    #[default]
+    Informational,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let severity = match self {
+            Severity::Critical => "Critical",
+            Severity::Major => "Major",
+            Severity::Medium => "Medium",
+            Severity::Minor => "Minor",
+            Severity::Informational => "Informational",
+        };
+        write!(f, "{}", severity)
+    }
+}
+
+fn normalize_repo(repo: &str) -> String {
+    let repo = repo.trim().to_lowercase();
+    let repo = repo
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.")
+        .trim_end_matches('/');
+    repo.trim_end_matches(".git").to_string()
+}
+
+fn is_path_prefix(prefix: &str, path: &str) -> bool {
+    path == prefix || path.starts_with(&format!("{}/", prefix))
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct CodeLocation {
+    pub repo: String,
+    pub commit: String,
+    pub path: String,
+    pub start_line: u64,
+    pub end_line: u64,
+}
+
+impl CodeLocation {
+    pub fn validate(&self, scope: &[String]) -> anyhow::Result<()> {
+        if self.path.trim().is_empty() {
+            anyhow::bail!("Location path is empty");
+        }
+
+        if self.repo.trim().is_empty() {
+            anyhow::bail!("Location repository is empty for {}", self.path);
+        }
+
+        if self.commit.trim().is_empty() {
+            anyhow::bail!("Location commit is empty for {}", self.path);
+        }
+
+        if self.start_line == 0 || self.end_line < self.start_line {
+            anyhow::bail!(
+                "Invalid line range {}-{} for {}",
+                self.start_line,
+                self.end_line,
+                self.path
+            );
+        }
+
+        let repo = normalize_repo(&self.repo);
+        // The location must be inside a scope entry, a parent of it would give a broken link
+        let in_scope = scope
+            .iter()
+            .any(|entry| is_path_prefix(&normalize_repo(entry), &repo));
+
+        if !scope.is_empty() && !in_scope {
+            anyhow::bail!("Repository {} is not in the audit scope", self.repo);
+        }
+
+        Ok(())
+    }
+
+    pub fn url(&self) -> String {
+        format!(
+            "{}/blob/{}/{}#L{}-L{}",
+            self.repo
+                .trim()
+                .trim_end_matches('/')
+                .trim_end_matches(".git"),
+            self.commit,
+            self.path.trim_start_matches('/'),
+            self.start_line,
+            self.end_line
+        )
+    }
+}
+
+impl std::fmt::Display for CodeLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let commit = self.commit.get(..7).unwrap_or(&self.commit);
+        if self.start_line == self.end_line {
+            write!(f, "{}:{}@{}", self.path, self.start_line, commit)
+        } else {
+            write!(
+                f,
+                "{}:{}-{}@{}",
+                self.path, self.start_line, self.end_line, commit
+            )
+        }
     }
 }
 
@@ -100,27 +230,69 @@ pub struct Issue<Id> {
     pub id: usize,
     pub name: String,
     pub description: String,
-    pub severity: String,
+    pub severity: Severity,
+    #[serde(default)]
+    pub cvss: Option<Cvss>,
 
     pub category: String,
     #[serde(default)]
     pub links: Vec<String>,
+    #[serde(default)]
+    pub locations: Vec<CodeLocation>,
 
     pub status: Status,
     pub include: bool,
 
     pub feedback: String,
     pub events: Vec<Event<Id>>,
+    #[serde(default)]
+    pub comments: Vec<Comment<Id>>,
+
+    #[serde(default)]
+    pub assignee: Option<String>,
+    #[serde(default)]
+    pub due_date: Option<i64>,
+    #[serde(default)]
+    pub remediation_note: Option<String>,
+    #[serde(default)]
+    pub overdue_notified: bool,
+
+    #[serde(default)]
+    pub status_history: Vec<IssueTransition>,
+
     #[serde(default = "default_timestamp")]
     pub last_modified: i64,
-    #[serde(default)]
-    pub read: HashMap<String, u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct IssueTransition {
+    pub status: Status,
+    pub timestamp: i64,
+}
+
+impl IssueTransition {
+    pub fn new(status: Status) -> Self {
+        Self {
+            status,
+            timestamp: Utc::now().timestamp(),
+        }
+    }
 }
 
 impl<T> Issue<T> {
     pub fn is_resolved(&self) -> bool {
         !self.include || self.status == Status::Fixed || self.status == Status::NotFixed
     }
+
+    pub fn set_status(&mut self, status: Status) {
+        self.status_history
+            .push(IssueTransition::new(status.clone()));
+        self.status = status;
+    }
+
+    pub fn is_overdue(&self, now: i64) -> bool {
+        !self.is_resolved() && self.due_date.is_some_and(|due_date| due_date < now)
+    }
 }
 
 impl Issue<String> {
@@ -130,14 +302,21 @@ impl Issue<String> {
             name: self.name,
             description: self.description,
             severity: self.severity,
+            cvss: self.cvss,
             category: self.category,
             links: self.links,
+            locations: self.locations,
             status: self.status,
             include: self.include,
             feedback: self.feedback,
             events: Event::parse_map(self.events),
+            comments: Comment::parse_map(self.comments),
+            assignee: self.assignee,
+            due_date: self.due_date,
+            remediation_note: self.remediation_note,
+            overdue_notified: self.overdue_notified,
+            status_history: self.status_history,
             last_modified: self.last_modified,
-            read: self.read,
         }
     }
 
@@ -153,14 +332,21 @@ impl Issue<ObjectId> {
             name: self.name,
             description: self.description,
             severity: self.severity,
+            cvss: self.cvss,
             category: self.category,
             links: self.links,
+            locations: self.locations,
             status: self.status,
             include: self.include,
             feedback: self.feedback,
             events: Event::to_string_map(self.events),
+            comments: Comment::to_string_map(self.comments),
+            assignee: self.assignee,
+            due_date: self.due_date,
+            remediation_note: self.remediation_note,
+            overdue_notified: self.overdue_notified,
+            status_history: self.status_history,
             last_modified: self.last_modified,
-            read: self.read,
         }
     }
 
@@ -182,18 +368,34 @@ pub struct ChangeIssue {
 
     pub category: Option<String>,
     pub links: Option<Vec<String>>,
+    pub locations: Option<Vec<CodeLocation>>,
 
-    pub severity: Option<String>,
+    pub severity: Option<Severity>,
+    pub cvss: Option<String>,
 
     pub status: Option<Action>,
     pub include: Option<bool>,
 
     pub feedback: Option<String>,
     pub events: Option<Vec<CreateEvent>>,
+
+    /// Remediation plan of the customer, empty values clear the field.
+    pub assignee: Option<String>,
+    pub due_date: Option<i64>,
+    pub remediation_note: Option<String>,
 }
 
 impl ChangeIssue {
+    pub fn changes_remediation(&self) -> bool {
+        self.assignee.is_some() || self.due_date.is_some() || self.remediation_note.is_some()
+    }
+
     pub fn get_access_auditor(&self, _audit: &Audit<ObjectId>) -> bool {
+        // The remediation plan belongs to the customer
+        if self.changes_remediation() {
+            return false;
+        }
+
         if let Some(action) = &self.status {
             action.is_auditor()
         } else {
@@ -217,7 +419,7 @@ impl<'a, 'b> AccessRules<&'a Audit<ObjectId>, &'b Auth> for ChangeIssue {
             Auth::Service(_, _) => true,
             Auth::Admin(_) => true,
             Auth::User(id) => {
-                if &object.auditor_id == id {
+                if object.is_editor(*id) {
                     self.get_access_auditor(object)
                 } else if &object.customer_id == id {
                     self.get_access_customer(object)
@@ -271,13 +473,85 @@ impl Event<ObjectId> {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CommentEdit {
+    pub text: String,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Comment<Id> {
+    pub id: Id,
+    pub parent: Option<Id>,
+    pub user: Id,
+    pub text: String,
+    pub timestamp: i64,
+    #[serde(default)]
+    pub edits: Vec<CommentEdit>,
+    #[serde(default)]
+    pub deleted: bool,
+    #[serde(default)]
+    pub mentions: Vec<Id>,
+    #[serde(default)]
+    pub read: HashMap<String, i64>,
+}
+
+impl<Id> Comment<Id> {
+    pub fn is_read_by(&self, user: &ObjectId) -> bool {
+        self.read.contains_key(&user.to_hex())
+    }
+}
+
+impl Comment<String> {
+    pub fn parse(self) -> Comment<ObjectId> {
+        Comment {
+            id: self.id.parse().unwrap(),
+            parent: self.parent.map(|parent| parent.parse().unwrap()),
+            user: self.user.parse().unwrap(),
+            text: self.text,
+            timestamp: self.timestamp,
+            edits: self.edits,
+            deleted: self.deleted,
+            mentions: self.mentions.iter().map(|id| id.parse().unwrap()).collect(),
+            read: self.read,
+        }
+    }
+
+    pub fn parse_map(map: Vec<Self>) -> Vec<Comment<ObjectId>> {
+        map.into_iter().map(|v| v.parse()).collect()
+    }
+}
+
+impl Comment<ObjectId> {
+    pub fn to_string(self) -> Comment<String> {
+        Comment {
+            id: self.id.to_hex(),
+            parent: self.parent.map(|parent| parent.to_hex()),
+            user: self.user.to_hex(),
+            text: self.text,
+            timestamp: self.timestamp,
+            edits: self.edits,
+            deleted: self.deleted,
+            mentions: self.mentions.iter().map(|id| id.to_hex()).collect(),
+            read: self.read,
+        }
+    }
+
+    pub fn to_string_map(map: Vec<Self>) -> Vec<Comment<String>> {
+        map.into_iter().map(|v| v.to_string()).collect()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum EventKind {
+    IssueCreated,
     IssueName,
     IssueDescription,
     IssueSeverity,
     IssueCategory,
     IssueLink,
+    IssueLocation,
+    IssueRemediation,
     StatusChange,
     Comment,
     FeedbackAdded,
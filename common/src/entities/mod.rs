@@ -1,12 +1,17 @@
+pub mod activity;
 pub mod audit;
 pub mod audit_request;
 pub mod auditor;
 pub mod badge;
 pub mod contacts;
 pub mod customer;
+pub mod cvss;
+pub mod finding_template;
 pub mod issue;
 pub mod letter;
 pub mod notification;
+pub mod organization;
 pub mod project;
+pub mod review_round;
 pub mod role;
 pub mod user;
@@ -0,0 +1,149 @@
+use mongodb::bson::{oid::ObjectId, Document};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    repository::Entity,
+    services::{API_PREFIX, AUDITORS_SERVICE, PROTOCOL},
+};
+
+use super::{audit_request::PriceRange, contacts::Contacts};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub enum OrganizationRole {
+    /// Manages the profile and the members, and assigns requests sent to the organization.
+    Admin,
+    Member,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct OrganizationMember<Id> {
+    pub user_id: Id,
+    pub role: OrganizationRole,
+}
+
+/// Auditing firm, its members share the profile, the pricing and the finding library.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct Organization<Id> {
+    pub id: Id,
+    pub name: String,
+    pub avatar: String,
+    pub about: String,
+    pub contacts: Contacts,
+    pub tags: Vec<String>,
+    pub price_range: PriceRange,
+    pub members: Vec<OrganizationMember<Id>>,
+    pub last_modified: i64,
+    pub created_at: i64,
+}
+
+impl Organization<String> {
+    pub fn parse(self) -> Organization<ObjectId> {
+        Organization {
+            id: self.id.parse().unwrap(),
+            name: self.name,
+            avatar: self.avatar,
+            about: self.about,
+            contacts: self.contacts,
+            tags: self.tags,
+            price_range: self.price_range,
+            members: self
+                .members
+                .into_iter()
+                .map(|member| OrganizationMember {
+                    user_id: member.user_id.parse().unwrap(),
+                    role: member.role,
+                })
+                .collect(),
+            last_modified: self.last_modified,
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl Organization<ObjectId> {
+    pub fn stringify(self) -> Organization<String> {
+        Organization {
+            id: self.id.to_hex(),
+            name: self.name,
+            avatar: self.avatar,
+            about: self.about,
+            contacts: self.contacts,
+            tags: self.tags,
+            price_range: self.price_range,
+            members: self
+                .members
+                .into_iter()
+                .map(|member| OrganizationMember {
+                    user_id: member.user_id.to_hex(),
+                    role: member.role,
+                })
+                .collect(),
+            last_modified: self.last_modified,
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl<Id: PartialEq> Organization<Id> {
+    pub fn is_member(&self, user_id: &Id) -> bool {
+        self.members.iter().any(|member| &member.user_id == user_id)
+    }
+
+    pub fn is_admin(&self, user_id: &Id) -> bool {
+        self.members
+            .iter()
+            .any(|member| &member.user_id == user_id && member.role == OrganizationRole::Admin)
+    }
+}
+
+impl Entity for Organization<ObjectId> {
+    fn id(&self) -> ObjectId {
+        self.id
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicOrganization {
+    pub id: String,
+    pub name: String,
+    pub avatar: String,
+    pub about: String,
+    pub contacts: Contacts,
+    pub tags: Vec<String>,
+    pub price_range: PriceRange,
+    pub members: Vec<OrganizationMember<String>>,
+    pub kind: String,
+}
+
+impl From<Organization<ObjectId>> for Option<Document> {
+    fn from(organization: Organization<ObjectId>) -> Self {
+        let organization = organization.stringify();
+        let mut document = mongodb::bson::to_document(&organization).unwrap();
+        if !organization.contacts.public_contacts {
+            document.remove("contacts");
+        }
+        document.insert(
+            "request_url",
+            format!(
+                "{}://{}/{}/organization/data",
+                PROTOCOL.as_str(),
+                AUDITORS_SERVICE.as_str(),
+                API_PREFIX.as_str(),
+            ),
+        );
+        document.insert(
+            "search_tags",
+            organization
+                .tags
+                .iter()
+                .map(|tag| tag.to_lowercase())
+                .collect::<Vec<String>>(),
+        );
+
+        document.remove("members");
+        document.remove("last_modified");
+        document.insert("kind", "organization");
+        Some(document)
+    }
+}
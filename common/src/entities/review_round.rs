@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+use super::audit_request::TimeRange;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Verdict {
+    #[serde(alias = "fixed")]
+    Fixed,
+    #[serde(alias = "partially_fixed", alias = "partiallyFixed")]
+    PartiallyFixed,
+    #[serde(alias = "not_fixed", alias = "notFixed")]
+    NotFixed,
+    #[serde(alias = "acknowledged")]
+    Acknowledged,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct IssueVerdict {
+    pub issue_id: usize,
+    pub verdict: Verdict,
+    #[serde(default)]
+    pub comment: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReviewRound {
+    pub id: usize,
+    pub commit: String,
+    pub scope: Vec<String>,
+    pub time: TimeRange,
+    #[serde(default)]
+    pub verdicts: Vec<IssueVerdict>,
+    pub created: i64,
+}
+
+impl ReviewRound {
+    pub fn verdict(&self, issue_id: usize) -> Option<&IssueVerdict> {
+        self.verdicts
+            .iter()
+            .find(|verdict| verdict.issue_id == issue_id)
+    }
+
+    pub fn set_verdict(&mut self, verdict: IssueVerdict) {
+        match self
+            .verdicts
+            .iter_mut()
+            .find(|v| v.issue_id == verdict.issue_id)
+        {
+            Some(existing) => *existing = verdict,
+            None => self.verdicts.push(verdict),
+        }
+    }
+}
@@ -1,7 +1,17 @@
+use actix_web::{http::header::ContentType, HttpResponse};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
 #[derive(Debug)]
 pub struct ServiceError {
     code: u16,
     err: anyhow::Error,
+    fields: Vec<FieldError>,
 }
 
 impl std::fmt::Display for ServiceError {
@@ -14,6 +24,19 @@ impl actix_web::error::ResponseError for ServiceError {
     fn status_code(&self) -> reqwest::StatusCode {
         reqwest::StatusCode::from_u16(self.code).unwrap()
     }
+
+    fn error_response(&self) -> HttpResponse {
+        if self.fields.is_empty() {
+            return HttpResponse::build(self.status_code())
+                .content_type(ContentType::plaintext())
+                .body(self.to_string());
+        }
+
+        HttpResponse::build(self.status_code()).json(serde_json::json!({
+            "message": self.err.to_string(),
+            "fields": self.fields,
+        }))
+    }
 }
 
 impl<E: Into<anyhow::Error>> From<E> for ServiceError {
@@ -21,6 +44,7 @@ impl<E: Into<anyhow::Error>> From<E> for ServiceError {
         ServiceError {
             code: 400,
             err: err.into(),
+            fields: Vec::new(),
         }
     }
 }
@@ -31,7 +55,47 @@ pub trait AddCode {
 
 impl AddCode for anyhow::Error {
     fn code(self, code: u16) -> ServiceError {
-        ServiceError { code, err: self }
+        ServiceError {
+            code,
+            err: self,
+            fields: Vec::new(),
+        }
+    }
+}
+
+/// Collects invalid fields, so that all of them are reported in one 400 response.
+#[derive(Debug, Default)]
+pub struct Validation {
+    fields: Vec<FieldError>,
+}
+
+impl Validation {
+    pub fn check(&mut self, valid: bool, field: &str, message: impl Into<String>) {
+        if !valid {
+            self.fields.push(FieldError {
+                field: field.to_string(),
+                message: message.into(),
+            });
+        }
+    }
+
+    pub fn finish(self) -> Result<()> {
+        if self.fields.is_empty() {
+            return Ok(());
+        }
+
+        let summary = self
+            .fields
+            .iter()
+            .map(|error| format!("{}: {}", error.field, error.message))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Err(ServiceError {
+            code: 400,
+            err: anyhow::anyhow!("Invalid fields: {}", summary),
+            fields: self.fields,
+        })
     }
 }
 
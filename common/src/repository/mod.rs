@@ -5,7 +5,7 @@ pub mod test_repository;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use mongodb::bson::{oid::ObjectId, Bson};
+use mongodb::bson::{oid::ObjectId, Bson, Document};
 
 use crate::error;
 
@@ -18,6 +18,55 @@ pub trait Repository<T> {
     async fn insert(&self, item: &T) -> error::Result<bool>;
     async fn find(&self, field: &str, value: &Bson) -> error::Result<Option<T>>;
     async fn delete(&self, field: &str, item: &ObjectId) -> error::Result<Option<T>>;
+    /// Replaces the document only if its `last_modified` still equals `version`.
+    /// Returns `false` when the document was changed or removed concurrently.
+    async fn replace(
+        &self,
+        field: &str,
+        id: &ObjectId,
+        version: i64,
+        item: &T,
+    ) -> error::Result<bool>;
+    async fn update(
+        &self,
+        field: &str,
+        id: &ObjectId,
+        set: Document,
+        push: Document,
+    ) -> error::Result<bool>;
+    /// Like `update`, but `$` in a path refers to the element of the embedded
+    /// `array` whose `id` equals `element_id`, e.g. `issues.$.events`.
+    async fn update_element(
+        &self,
+        field: &str,
+        id: &ObjectId,
+        array: &str,
+        element_id: &Bson,
+        set: Document,
+        push: Document,
+    ) -> error::Result<bool>;
+    /// Like `update_element`, but `$[nested]` in a path refers to the elements of an
+    /// array inside it that match `nested_filter`, e.g. `issues.$.comments.$[nested].text`
+    /// with `{"nested.id": comment_id}`.
+    #[allow(clippy::too_many_arguments)]
+    async fn update_nested_element(
+        &self,
+        field: &str,
+        id: &ObjectId,
+        array: &str,
+        element_id: &Bson,
+        nested_filter: Document,
+        set: Document,
+        push: Document,
+    ) -> error::Result<bool>;
+    /// Atomically increments `counter` by `amount` and returns its new value.
+    async fn increment(
+        &self,
+        field: &str,
+        id: &ObjectId,
+        counter: &str,
+        amount: i64,
+    ) -> error::Result<Option<i64>>;
     async fn find_many(&self, field: &str, value: &Bson) -> error::Result<Vec<T>>;
     async fn find_many_limit(
         &self,
@@ -26,6 +75,14 @@ pub trait Repository<T> {
         skip: i32,
         limit: i32,
     ) -> error::Result<(Vec<T>, u64)>;
+    /// Like `find_many_limit`, but with an arbitrary filter and sort order.
+    async fn find_page(
+        &self,
+        filter: Document,
+        sort: Document,
+        skip: i32,
+        limit: i32,
+    ) -> error::Result<(Vec<T>, u64)>;
     async fn find_all(&self, skip: u32, limit: u32) -> error::Result<Vec<T>>;
     async fn get_all_since(&self, since: i64) -> error::Result<Vec<T>>;
     async fn find_all_by_ids(&self, id: &str, ids: Vec<ObjectId>) -> error::Result<Vec<T>>;
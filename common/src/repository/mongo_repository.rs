@@ -1,8 +1,8 @@
 use async_trait::async_trait;
 use futures::StreamExt;
 use mongodb::{
-    bson::{doc, oid::ObjectId, Bson},
-    options::FindOptions,
+    bson::{doc, oid::ObjectId, Bson, Document},
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument, UpdateOptions},
 };
 use serde::{de::DeserializeOwned, Serialize};
 
@@ -10,6 +10,17 @@ use crate::error;
 
 use super::{Entity, Repository};
 
+fn update_document(set: Document, push: Document) -> Document {
+    let mut update = Document::new();
+    if !set.is_empty() {
+        update.insert("$set", set);
+    }
+    if !push.is_empty() {
+        update.insert("$push", push);
+    }
+    update
+}
+
 pub struct MongoRepository<T> {
     pub collection: mongodb::Collection<T>,
 }
@@ -56,6 +67,97 @@ where
         Ok(result)
     }
 
+    async fn replace(
+        &self,
+        field: &str,
+        id: &ObjectId,
+        version: i64,
+        item: &T,
+    ) -> error::Result<bool> {
+        let result = self
+            .collection
+            .replace_one(doc! {field: id, "last_modified": version}, item, None)
+            .await?;
+        Ok(result.matched_count == 1)
+    }
+
+    async fn update(
+        &self,
+        field: &str,
+        id: &ObjectId,
+        set: Document,
+        push: Document,
+    ) -> error::Result<bool> {
+        let result = self
+            .collection
+            .update_one(doc! {field: id}, update_document(set, push), None)
+            .await?;
+        Ok(result.matched_count == 1)
+    }
+
+    async fn update_element(
+        &self,
+        field: &str,
+        id: &ObjectId,
+        array: &str,
+        element_id: &Bson,
+        set: Document,
+        push: Document,
+    ) -> error::Result<bool> {
+        let filter = doc! {field: id, format!("{}.id", array): element_id};
+        let result = self
+            .collection
+            .update_one(filter, update_document(set, push), None)
+            .await?;
+        Ok(result.matched_count == 1)
+    }
+
+    async fn update_nested_element(
+        &self,
+        field: &str,
+        id: &ObjectId,
+        array: &str,
+        element_id: &Bson,
+        nested_filter: Document,
+        set: Document,
+        push: Document,
+    ) -> error::Result<bool> {
+        let filter = doc! {field: id, format!("{}.id", array): element_id};
+        let options = UpdateOptions::builder()
+            .array_filters(vec![nested_filter])
+            .build();
+        let result = self
+            .collection
+            .update_one(filter, update_document(set, push), options)
+            .await?;
+        Ok(result.matched_count == 1)
+    }
+
+    async fn increment(
+        &self,
+        field: &str,
+        id: &ObjectId,
+        counter: &str,
+        amount: i64,
+    ) -> error::Result<Option<i64>> {
+        let options = FindOneAndUpdateOptions::builder()
+            .projection(doc! {counter: 1})
+            .return_document(ReturnDocument::After)
+            .build();
+
+        let result = self
+            .collection
+            .clone_with_type::<Document>()
+            .find_one_and_update(doc! {field: id}, doc! {"$inc": {counter: amount}}, options)
+            .await?;
+
+        Ok(result.and_then(|doc| match doc.get(counter) {
+            Some(Bson::Int32(value)) => Some(*value as i64),
+            Some(Bson::Int64(value)) => Some(*value),
+            _ => None,
+        }))
+    }
+
     async fn find_all(&self, skip: u32, limit: u32) -> error::Result<Vec<T>> {
         let find_options = FindOptions::builder()
             .skip(skip as u64)
@@ -108,6 +210,34 @@ where
         Ok((result.into_iter().collect::<mongodb::error::Result<_>>()?, total_documents))
     }
 
+    async fn find_page(
+        &self,
+        filter: Document,
+        sort: Document,
+        skip: i32,
+        limit: i32,
+    ) -> error::Result<(Vec<T>, u64)> {
+        let find_options = FindOptions::builder()
+            .sort(sort)
+            .skip(skip as u64)
+            .limit(limit as i64)
+            .build();
+
+        let result: Vec<mongodb::error::Result<T>> = self
+            .collection
+            .find(filter.clone(), find_options)
+            .await?
+            .collect()
+            .await;
+
+        let total_documents = self.collection.count_documents(filter, None).await?;
+
+        Ok((
+            result.into_iter().collect::<mongodb::error::Result<_>>()?,
+            total_documents,
+        ))
+    }
+
     async fn get_all_since(&self, since: i64) -> error::Result<Vec<T>> {
         let result: Vec<mongodb::error::Result<T>> = self
             .collection
@@ -1,13 +1,151 @@
 use std::sync::Mutex;
 
 use async_trait::async_trait;
-use mongodb::bson::{self, oid::ObjectId, Bson};
+use mongodb::bson::{self, oid::ObjectId, Bson, Document};
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::error;
 
 use super::{Entity, Repository};
 
+fn path_entry<'a>(mut value: &'a mut Bson, path: &str, position: Option<usize>) -> &'a mut Bson {
+    for key in path.split('.') {
+        value = match value {
+            Bson::Array(array) => {
+                let idx = if key == "$" {
+                    position.unwrap()
+                } else {
+                    key.parse().unwrap()
+                };
+                &mut array[idx]
+            }
+            Bson::Document(doc) => doc.entry(key.to_string()).or_insert(Bson::Null),
+            _ => panic!("Invalid path {}", path),
+        };
+    }
+    value
+}
+
+/// Entries of a path, `$[nested]` goes through the elements of the array that match `nested`.
+fn path_entries<'a>(
+    item: &'a mut Bson,
+    path: &str,
+    position: Option<usize>,
+    nested: Option<&Document>,
+) -> Vec<&'a mut Bson> {
+    let Some((prefix, suffix)) = path.split_once(".$[nested].") else {
+        return vec![path_entry(item, path, position)];
+    };
+
+    let filter: Document = nested
+        .unwrap()
+        .iter()
+        .map(|(key, value)| (key.trim_start_matches("nested.").to_string(), value.clone()))
+        .collect();
+
+    let Bson::Array(array) = path_entry(item, prefix, position) else {
+        panic!("{} is not an array", prefix);
+    };
+
+    array
+        .iter_mut()
+        .filter(|element| matches(element, &filter))
+        .map(|element| path_entry(element, suffix, None))
+        .collect()
+}
+
+fn apply_update(
+    item: &mut Bson,
+    set: Document,
+    push: Document,
+    position: Option<usize>,
+    nested: Option<&Document>,
+) {
+    for (path, value) in set {
+        for target in path_entries(item, &path, position, nested) {
+            *target = value.clone();
+        }
+    }
+
+    for (path, value) in push {
+        for target in path_entries(item, &path, position, nested) {
+            push_value(target, &path, value.clone());
+        }
+    }
+}
+
+fn push_value(target: &mut Bson, path: &str, value: Bson) {
+    if target == &Bson::Null {
+        *target = Bson::Array(Vec::new());
+    }
+    let Bson::Array(array) = target else {
+        panic!("{} is not an array", path);
+    };
+    match value {
+        Bson::Document(each) if each.contains_key("$each") => {
+            array.extend(each.get_array("$each").unwrap().iter().cloned())
+        }
+        value => array.push(value),
+    }
+}
+
+fn as_i64(value: &Bson) -> Option<i64> {
+    match value {
+        Bson::Int32(value) => Some(*value as i64),
+        Bson::Int64(value) => Some(*value),
+        _ => None,
+    }
+}
+
+/// Field of a dotted path, paths through arrays of documents collect the field of every element.
+fn lookup(value: &Bson, key: &str) -> Option<Bson> {
+    match value {
+        Bson::Document(doc) => doc.get(key).cloned(),
+        Bson::Array(array) => Some(Bson::Array(
+            array
+                .iter()
+                .filter_map(|element| element.as_document()?.get(key).cloned())
+                .collect(),
+        )),
+        _ => None,
+    }
+}
+
+/// Supports equality (array fields match when they contain the value), `$gte`, `$lte`, `$in`
+/// and `$or`.
+fn matches(item: &Bson, filter: &Document) -> bool {
+    filter.iter().all(|(path, condition)| {
+        if path == "$or" {
+            return condition
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|alternative| matches(item, alternative.as_document().unwrap()));
+        }
+
+        let mut value = Some(item.clone());
+        for key in path.split('.') {
+            value = value.and_then(|value| lookup(&value, key));
+        }
+        let value = value.unwrap_or(Bson::Null);
+
+        match condition {
+            Bson::Document(ops) if ops.keys().all(|key| key.starts_with('$')) => {
+                ops.iter().all(|(op, bound)| match op.as_str() {
+                    "$gte" => as_i64(&value) >= as_i64(bound),
+                    "$lte" => as_i64(&value).is_some() && as_i64(&value) <= as_i64(bound),
+                    "$in" => bound.as_array().unwrap().contains(&value),
+                    _ => panic!("Unsupported operator {}", op),
+                })
+            }
+            condition => match &value {
+                Bson::Array(array) => array.contains(condition),
+                value => value == condition,
+            },
+        }
+    })
+}
+
 pub struct TestRepository<T> {
     _t: std::marker::PhantomData<T>,
     pub db: Mutex<Vec<Bson>>,
@@ -71,6 +209,141 @@ where
         Ok(result)
     }
 
+    async fn replace(
+        &self,
+        field: &str,
+        id: &ObjectId,
+        version: i64,
+        item: &T,
+    ) -> error::Result<bool> {
+        let mut db = self.db.lock().unwrap();
+        let pos = db.iter().position(|x| {
+            let doc = x.as_document().unwrap();
+            &doc.get_object_id(field).unwrap() == id
+                && doc.get_i64("last_modified").unwrap() == version
+        });
+
+        if let Some(pos) = pos {
+            db[pos] = bson::to_bson(&item).unwrap();
+        }
+
+        Ok(pos.is_some())
+    }
+
+    async fn update(
+        &self,
+        field: &str,
+        id: &ObjectId,
+        set: Document,
+        push: Document,
+    ) -> error::Result<bool> {
+        let mut db = self.db.lock().unwrap();
+        let item = db
+            .iter_mut()
+            .find(|x| &x.as_document().unwrap().get_object_id(field).unwrap() == id);
+
+        if let Some(item) = item {
+            apply_update(item, set, push, None, None);
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    async fn update_element(
+        &self,
+        field: &str,
+        id: &ObjectId,
+        array: &str,
+        element_id: &Bson,
+        set: Document,
+        push: Document,
+    ) -> error::Result<bool> {
+        let mut db = self.db.lock().unwrap();
+        let item = db
+            .iter_mut()
+            .find(|x| &x.as_document().unwrap().get_object_id(field).unwrap() == id);
+
+        let Some(item) = item else {
+            return Ok(false);
+        };
+
+        let position = item
+            .as_document()
+            .unwrap()
+            .get_array(array)
+            .unwrap()
+            .iter()
+            .position(|x| x.as_document().unwrap().get("id") == Some(element_id));
+
+        if let Some(position) = position {
+            apply_update(item, set, push, Some(position), None);
+        }
+
+        Ok(position.is_some())
+    }
+
+    async fn update_nested_element(
+        &self,
+        field: &str,
+        id: &ObjectId,
+        array: &str,
+        element_id: &Bson,
+        nested_filter: Document,
+        set: Document,
+        push: Document,
+    ) -> error::Result<bool> {
+        let mut db = self.db.lock().unwrap();
+        let item = db
+            .iter_mut()
+            .find(|x| &x.as_document().unwrap().get_object_id(field).unwrap() == id);
+
+        let Some(item) = item else {
+            return Ok(false);
+        };
+
+        let position = item
+            .as_document()
+            .unwrap()
+            .get_array(array)
+            .unwrap()
+            .iter()
+            .position(|x| x.as_document().unwrap().get("id") == Some(element_id));
+
+        if let Some(position) = position {
+            apply_update(item, set, push, Some(position), Some(&nested_filter));
+        }
+
+        Ok(position.is_some())
+    }
+
+    async fn increment(
+        &self,
+        field: &str,
+        id: &ObjectId,
+        counter: &str,
+        amount: i64,
+    ) -> error::Result<Option<i64>> {
+        let mut db = self.db.lock().unwrap();
+        let item = db
+            .iter_mut()
+            .find(|x| &x.as_document().unwrap().get_object_id(field).unwrap() == id);
+
+        let Some(item) = item else {
+            return Ok(None);
+        };
+
+        let value = path_entry(item, counter, None);
+        let next = match value {
+            Bson::Int32(value) => *value as i64 + amount,
+            Bson::Int64(value) => *value + amount,
+            _ => amount,
+        };
+        *value = Bson::Int64(next);
+
+        Ok(Some(next))
+    }
+
     async fn find_all(&self, skip: u32, limit: u32) -> error::Result<Vec<T>> {
         let db = self.db.lock().unwrap();
         Ok(db
@@ -109,6 +382,40 @@ where
         Ok((result[skip..=limit].to_vec(), db.len() as u64))
     }
 
+    async fn find_page(
+        &self,
+        filter: Document,
+        sort: Document,
+        skip: i32,
+        limit: i32,
+    ) -> error::Result<(Vec<T>, u64)> {
+        let db = self.db.lock().unwrap();
+        let mut result: Vec<&Bson> = db.iter().filter(|x| matches(x, &filter)).collect();
+
+        for (field, direction) in sort.iter().collect::<Vec<_>>().into_iter().rev() {
+            let key = |x: &Bson| x.as_document().unwrap().get(field).and_then(as_i64);
+            let descending = as_i64(direction) == Some(-1);
+            result.sort_by(|a, b| {
+                let ordering = key(a).cmp(&key(b));
+                if descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+
+        let total_documents = result.len() as u64;
+        let result = result
+            .into_iter()
+            .skip(skip as usize)
+            .take(limit as usize)
+            .map(|x| bson::from_bson(x.clone()).unwrap())
+            .collect();
+
+        Ok((result, total_documents))
+    }
+
     async fn get_all_since(&self, since: i64) -> error::Result<Vec<T>> {
         let db = self.db.lock().unwrap();
         Ok(db
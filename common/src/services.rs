@@ -15,6 +15,8 @@ lazy_static! {
     pub static ref USERS_SERVICE: String = var("USERS_SERVICE_URL").unwrap();
     pub static ref EVENTS_SERVICE: String = var("EVENTS_SERVICE_URL").unwrap();
     pub static ref CHAT_SERVICE: String = var("EVENTS_SERVICE_URL").unwrap();
+    /// Optional, lines of code are not counted without it
+    pub static ref CLOC_SERVICE: Option<String> = var("CLOC_SERVICE_URL").ok();
     pub static ref REPORT_SERVICE: String = var("REPORT_SERVICE_URL").unwrap();
     pub static ref API_PREFIX: String = var("API_PREFIX").unwrap();
     pub static ref FRONTEND: String = var("FRONTEND").unwrap();
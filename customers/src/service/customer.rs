@@ -143,7 +143,7 @@ impl CustomerService {
             let mut iter = user.name.split(' ');
 
             let first_name = iter.next().unwrap();
-            let last_name = iter.last().unwrap_or_default();
+            let last_name = iter.next_back().unwrap_or_default();
 
             let customer = CreateCustomer {
                 avatar: None,
@@ -1,5 +1,6 @@
 use common::{
     access_rules::{AccessRules, GetData},
+    auth::{Auth, Service},
     context::GeneralContext,
     entities::{
         customer::{Customer, PublicCustomer},
@@ -79,10 +80,13 @@ impl IndexerService {
 
         let projects = projects.find_all_by_ids("id", ids).await?;
 
+        // Unpublished projects are only returned to the services listing audits of them
+        let private_access = matches!(auth, Auth::Service(Service::Audits, _));
+
         Ok(projects
             .into_iter()
             .map(|x| auth.public_project(x))
-            .filter(|x: &PublicProject| x.publish_options.publish)
+            .filter(|x: &PublicProject| x.publish_options.publish || private_access)
             .collect::<Vec<_>>())
     }
 }
@@ -4,7 +4,7 @@ use common::entities::customer::PublicCustomer;
 use common::services::API_PREFIX;
 use common::{
     access_rules::{AccessRules, Edit, Read},
-    api::seartch::PaginationParams,
+    api::seartch::{PaginationParams, MAX_PER_PAGE},
     context::GeneralContext,
     entities::{
         contacts::Contacts,
@@ -137,10 +137,7 @@ impl ProjectService {
         &self,
         pagination: PaginationParams,
     ) -> error::Result<Vec<Project<String>>> {
-        let page = pagination.page.unwrap_or(0);
-        let per_page = pagination.per_page.unwrap_or(0);
-        let limit = pagination.per_page.unwrap_or(1000);
-        let skip = (page - 1) * per_page;
+        let (skip, limit) = pagination.skip_limit(MAX_PER_PAGE)?;
 
         let auth = self.context.auth();
 
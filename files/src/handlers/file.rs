@@ -34,20 +34,20 @@ pub async fn create_file(
             "path" => {
                 while let Some(chunk) = field.next().await {
                     let data = chunk.unwrap();
-                    path.push_str(&String::from_utf8(data.to_vec()).unwrap());
+                    path.push_str(std::str::from_utf8(&data).unwrap());
                 }
             }
             "original_name" => {
                 while let Some(chunk) = field.next().await {
                     let data = chunk.unwrap();
-                    original_name.push_str(&String::from_utf8(data.to_vec()).unwrap());
+                    original_name.push_str(std::str::from_utf8(&data).unwrap());
                 }
             }
             "private" => {
                 let mut str = String::new();
                 while let Some(chunk) = field.next().await {
                     let data = chunk.unwrap();
-                    str.push_str(&String::from_utf8(data.to_vec()).unwrap());
+                    str.push_str(std::str::from_utf8(&data).unwrap());
                 }
 
                 private = str == "true";
@@ -55,20 +55,20 @@ pub async fn create_file(
             "customerId" => {
                 while let Some(chunk) = field.next().await {
                     let data = chunk.unwrap();
-                    customer_id.push_str(&String::from_utf8(data.to_vec()).unwrap());
+                    customer_id.push_str(std::str::from_utf8(&data).unwrap());
                 }
             }
             "auditorId" => {
                 while let Some(chunk) = field.next().await {
                     let data = chunk.unwrap();
-                    auditor_id.push_str(&String::from_utf8(data.to_vec()).unwrap());
+                    auditor_id.push_str(std::str::from_utf8(&data).unwrap());
                 }
             }
 
             "full_access" => {
                 while let Some(chunk) = field.next().await {
                     let data = chunk.unwrap();
-                    full_access.push_str(&String::from_utf8(data.to_vec()).unwrap());
+                    full_access.push_str(std::str::from_utf8(&data).unwrap());
                 }
             }
             _ => (),
@@ -95,7 +95,7 @@ impl MailService {
                 MultiPart::related().singlepart(
                     SinglePart::builder()
                         .header(header::ContentType::TEXT_HTML)
-                        .body(String::from(letter.message)),
+                        .body(letter.message),
                 ),
             );
 
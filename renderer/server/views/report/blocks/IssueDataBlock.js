@@ -22,6 +22,14 @@ const IssueDataBlock = ({ data, num, subsectionLevel }) => {
           <span className="issue-info-title">Severity:</span>
           <SeverityChip severity={data?.issue_data?.severity} />
         </div>
+        {data?.issue_data?.cvss && (
+          <div className="issue-info-wrapper">
+            <span className="issue-info-title">CVSS:</span>
+            <span>
+              {data.issue_data.cvss.score} ({data.issue_data.cvss.vector})
+            </span>
+          </div>
+        )}
         {data?.issue_data?.category && (
           <div className="issue-info-wrapper">
             <span className="issue-info-title">Category:</span>
@@ -34,12 +42,37 @@ const IssueDataBlock = ({ data, num, subsectionLevel }) => {
             {data?.issue_data?.status?.replace(/(?<=[a-z])([A-Z])/g, ' $1')}
           </span>
         </div>
+        {!!data?.issue_data?.review?.length && (
+          <div className="issue-info-wrapper">
+            <span className="issue-info-title">Review:</span>
+            <span>
+              Initial finding
+              {data.issue_data.review.map((review, idx) => (
+                <span key={idx}>
+                  {' → '}Round {review.round} ({review.commit}):{' '}
+                  {review.verdict.replace(/(?<=[a-z])([A-Z])/g, ' $1')}
+                  {review.comment && ` (${review.comment})`}
+                </span>
+              ))}
+            </span>
+          </div>
+        )}
       </div>
 
       <div className="project-description">
         <RenderMarkdown markdown={data?.text} />
       </div>
 
+      {!!data?.issue_data?.locations?.length && (
+        <div className="scope issue-links">
+          {data.issue_data.locations.map((location, idx) => (
+            <a href={location.url} key={idx} className="issue-link">
+              {location.text}
+            </a>
+          ))}
+        </div>
+      )}
+
       {!!data?.issue_data?.links?.length && (
         <div className="scope issue-links">
           {data?.issue_data?.links?.map((link, idx) => (
@@ -26,6 +26,9 @@ const StatisticsBlock = ({ data, num, subsectionLevel }) => {
             <th>
               <SeverityChip severity="Minor" />
             </th>
+            <th>
+              <SeverityChip severity="Informational" />
+            </th>
           </tr>
         </thead>
         <tbody>
@@ -35,6 +38,7 @@ const StatisticsBlock = ({ data, num, subsectionLevel }) => {
             <td>{data?.statistics?.fixed?.major}</td>
             <td>{data?.statistics?.fixed?.medium}</td>
             <td>{data?.statistics?.fixed?.minor}</td>
+            <td>{data?.statistics?.fixed?.informational}</td>
           </tr>
           <tr>
             <td className="table-heading">Not Fixed</td>
@@ -42,6 +46,7 @@ const StatisticsBlock = ({ data, num, subsectionLevel }) => {
             <td>{data?.statistics?.not_fixed?.major}</td>
             <td>{data?.statistics?.not_fixed?.medium}</td>
             <td>{data?.statistics?.not_fixed?.minor}</td>
+            <td>{data?.statistics?.not_fixed?.informational}</td>
           </tr>
         </tbody>
       </table>
@@ -6,7 +6,11 @@ use common::{
     },
     auth::{Auth, Service},
     context::GeneralContext,
-    entities::issue::Status,
+    entities::{
+        cvss::Cvss,
+        issue::{Severity, Status},
+        review_round::ReviewRound,
+    },
     services::{API_PREFIX, FILES_SERVICE, FRONTEND, PROTOCOL, RENDERER_SERVICE, USERS_SERVICE},
 };
 use reqwest::multipart::{Form, Part};
@@ -15,9 +19,26 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct IssueData {
     pub severity: Option<String>,
+    pub cvss: Option<Cvss>,
     pub status: String,
     pub category: Option<String>,
     pub links: Vec<String>,
+    pub locations: Vec<IssueLocation>,
+    pub review: Vec<IssueReview>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IssueLocation {
+    pub text: String,
+    pub url: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IssueReview {
+    pub round: usize,
+    pub commit: String,
+    pub verdict: String,
+    pub comment: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -49,6 +70,19 @@ pub struct IssuesCount<T> {
     major: T,
     medium: T,
     minor: T,
+    informational: T,
+}
+
+impl<T> IssuesCount<T> {
+    fn get_mut(&mut self, severity: Severity) -> &mut T {
+        match severity {
+            Severity::Critical => &mut self.critical,
+            Severity::Major => &mut self.major,
+            Severity::Medium => &mut self.medium,
+            Severity::Minor => &mut self.minor,
+            Severity::Informational => &mut self.informational,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -57,18 +91,12 @@ pub struct IssueCollector {
 }
 
 impl IssueCollector {
-    pub fn add_issue(mut self, issue: &PublicIssue) -> Self {
-        let Some(section) = generate_issue_section(issue) else {
+    pub fn add_issue(mut self, issue: &PublicIssue, rounds: &[ReviewRound]) -> Self {
+        let Some(section) = generate_issue_section(issue, rounds) else {
             return self;
         };
 
-        match issue.severity.as_str() {
-            "Critical" => self.issues.critical.push(section),
-            "Major" => self.issues.major.push(section),
-            "Medium" => self.issues.medium.push(section),
-            "Minor" => self.issues.minor.push(section),
-            _ => {}
-        };
+        self.issues.get_mut(issue.severity).push(section);
 
         self
     }
@@ -78,6 +106,7 @@ impl IssueCollector {
             ("Major", &self.issues.major),
             ("Medium", &self.issues.medium),
             ("Minor", &self.issues.minor),
+            ("Informational", &self.issues.informational),
         ];
 
         sections
@@ -118,21 +147,9 @@ impl Statistics {
                 statistics.total += 1;
 
                 if issue.status == Status::Fixed {
-                    match issue.severity.as_str() {
-                        "Critical" => statistics.fixed.critical += 1,
-                        "Major" => statistics.fixed.major += 1,
-                        "Medium" => statistics.fixed.medium += 1,
-                        "Minor" => statistics.fixed.minor += 1,
-                        _ => {}
-                    }
+                    *statistics.fixed.get_mut(issue.severity) += 1;
                 } else {
-                    match issue.severity.as_str() {
-                        "Critical" => statistics.not_fixed.critical += 1,
-                        "Major" => statistics.not_fixed.major += 1,
-                        "Medium" => statistics.not_fixed.medium += 1,
-                        "Minor" => statistics.not_fixed.minor += 1,
-                        _ => {}
-                    }
+                    *statistics.not_fixed.get_mut(issue.severity) += 1;
                 }
             }
         }
@@ -141,12 +158,14 @@ impl Statistics {
     }
 }
 
-fn generate_issue_section(issue: &PublicIssue) -> Option<Section> {
+fn generate_issue_section(issue: &PublicIssue, rounds: &[ReviewRound]) -> Option<Section> {
     if !issue.include {
         return None;
     }
 
     let PublicIssue {
+        id,
+        code,
         name,
         status,
         category,
@@ -169,29 +188,47 @@ fn generate_issue_section(issue: &PublicIssue) -> Option<Section> {
         None
     };
 
-    let severity = if !severity.is_empty() {
-        Some(severity.clone())
-    } else {
-        None
-    };
-
     let category = if !category.is_empty() {
         Some(category.clone())
     } else {
         None
     };
 
+    let review = rounds
+        .iter()
+        .filter_map(|round| {
+            round.verdict(*id).map(|verdict| IssueReview {
+                round: round.id,
+                commit: round.commit.clone(),
+                verdict: format!("{:?}", verdict.verdict),
+                comment: Some(verdict.comment.clone()).filter(|comment| !comment.is_empty()),
+            })
+        })
+        .collect();
+
+    let locations = issue
+        .locations
+        .iter()
+        .map(|location| IssueLocation {
+            text: location.to_string(),
+            url: location.url(),
+        })
+        .collect();
+
     Some(Section {
         typ: "issue_data".to_string(),
-        title: name.clone(),
+        title: format!("{}: {}", code, name),
         text: description.clone(),
         include_in_toc: true,
         feedback,
         issue_data: Some(IssueData {
-            severity,
+            severity: Some(severity.to_string()),
+            cvss: issue.cvss.clone(),
             status,
             category,
             links: issue.links.clone(),
+            locations,
+            review,
         }),
         ..Default::default()
     })
@@ -209,6 +246,24 @@ fn generate_audit_sections(audit: &PublicAudit, issues: Vec<Section>) -> Vec<Sec
      */
     let disclaimer = include_str!("../../templates/disclaimer.md").to_string();
 
+    let mut summary = vec![
+        Section {
+            typ: "project_description".to_string(),
+            title: "Project Description".to_string(),
+            text: audit.description.clone(),
+            include_in_toc: true,
+            ..Default::default()
+        },
+        Section {
+            typ: "scope".to_string(),
+            title: "Scope".to_string(),
+            links: Some(audit.scope.clone()),
+            include_in_toc: true,
+            ..Default::default()
+        },
+    ];
+    summary.extend(generate_review_rounds_section(&audit.review_rounds));
+
     vec![
         Section {
             typ: "markdown".to_owned(),
@@ -221,22 +276,7 @@ fn generate_audit_sections(audit: &PublicAudit, issues: Vec<Section>) -> Vec<Sec
             typ: "plain_text".to_string(),
             title: "Summary".to_string(),
             include_in_toc: true,
-            subsections: Some(vec![
-                Section {
-                    typ: "project_description".to_string(),
-                    title: "Project Description".to_string(),
-                    text: audit.description.clone(),
-                    include_in_toc: true,
-                    ..Default::default()
-                },
-                Section {
-                    typ: "scope".to_string(),
-                    title: "Scope".to_string(),
-                    links: Some(audit.scope.clone()),
-                    include_in_toc: true,
-                    ..Default::default()
-                },
-            ]),
+            subsections: Some(summary),
             ..Default::default()
         },
         Section {
@@ -256,12 +296,45 @@ fn generate_audit_sections(audit: &PublicAudit, issues: Vec<Section>) -> Vec<Sec
     ]
 }
 
+fn generate_review_rounds_section(rounds: &[ReviewRound]) -> Option<Section> {
+    if rounds.is_empty() {
+        return None;
+    }
+
+    let text = rounds
+        .iter()
+        .map(|round| {
+            let scope: String = round
+                .scope
+                .iter()
+                .map(|link| format!("\n- {}", link))
+                .collect();
+            format!(
+                "**Round {}** at commit `{}`, {} verdicts\n{}",
+                round.id,
+                round.commit,
+                round.verdicts.len(),
+                scope
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    Some(Section {
+        typ: "markdown".to_string(),
+        title: "Review Rounds".to_string(),
+        text,
+        include_in_toc: true,
+        ..Default::default()
+    })
+}
+
 fn generate_data(audit: &PublicAudit) -> Vec<Section> {
     let issues = audit
         .issues
         .iter()
         .fold(IssueCollector::default(), |collector, i| {
-            collector.add_issue(i)
+            collector.add_issue(i, &audit.review_rounds)
         })
         .into_issues();
     generate_audit_sections(audit, issues)
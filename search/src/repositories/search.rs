@@ -55,7 +55,7 @@ impl SearchRepo {
         let kind = query
             .kind
             .clone()
-            .unwrap_or(String::new())
+            .unwrap_or_default()
             .split(' ')
             .filter_map(|s| {
                 if !s.is_empty() {
@@ -74,7 +74,7 @@ impl SearchRepo {
                 sort.insert("kind", 1);
             }
 
-            sort.insert(sort_by.clone(), sort_order.clone());
+            sort.insert(sort_by.clone(), sort_order);
             if sort_by == "price" {
                 let sort_field = if sort_order == 1 {
                     "price_range.to"
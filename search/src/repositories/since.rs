@@ -48,6 +48,15 @@ impl Default for Since {
                     ),
                     0,
                 );
+                map.insert(
+                    format!(
+                        "{}://{}/{}/organization/data",
+                        PROTOCOL.as_str(),
+                        AUDITORS_SERVICE.as_str(),
+                        API_PREFIX.as_str(),
+                    ),
+                    0,
+                );
                 map.insert(
                     format!(
                         "{}://{}/{}/auditor/data",
@@ -133,7 +133,7 @@ impl SearchService {
         for (i, doc) in result.iter().enumerate() {
             let id = ObjectId::from_str(doc.get_str("id").unwrap()).unwrap();
             let service = doc.get_str("request_url").unwrap();
-            let vecs = ids.entry(service.to_string()).or_insert(Vec::new());
+            let vecs = ids.entry(service.to_string()).or_default();
 
             indexes.insert(id, i);
             vecs.push(id);
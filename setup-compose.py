@@ -76,7 +76,7 @@ services:
       - 3003%optional_duplicate%
     environment:
       VIRTUAL_HOST: "${VIRTUAL_HOST}"
-      VIRTUAL_PATH: ~^/%API_PREFIX%/(audit|my_audit|request|my_request|public_audits|no_customer_audit)
+      VIRTUAL_PATH: ~^/%API_PREFIX%/(audit|my_audit|request|my_request|public_audits|no_customer_audit|finding_template|my_finding_template|analytics|public/audit)
       <<: *common-variables
     networks:
       - %proxy_network%
@@ -92,7 +92,7 @@ services:
       - %volume_namespace%-binaries:/data/binaries
     environment:
       VIRTUAL_HOST: "${VIRTUAL_HOST}"
-      VIRTUAL_PATH: ~^/%API_PREFIX%/(auditor|my_auditor|badge)
+      VIRTUAL_PATH: ~^/%API_PREFIX%/(auditor|my_auditor|badge|organization|my_organization)
       <<: *common-variables
     networks:
       - %proxy_network%
@@ -1,9 +1,10 @@
 use actix_web::{
-    delete, get, patch,
+    delete, get, patch, post,
     web::{Json, Path},
     HttpResponse,
 };
 use common::{context::GeneralContext, entities::user::PublicUser, error};
+use mongodb::bson::oid::ObjectId;
 use serde_json::json;
 
 use crate::service::user::{UserChange, UserService};
@@ -31,6 +32,14 @@ pub async fn find_user(context: GeneralContext, id: Path<String>) -> error::Resu
     }
 }
 
+#[post("/user/data")]
+pub async fn get_user_data(
+    context: GeneralContext,
+    Json(ids): Json<Vec<ObjectId>>,
+) -> error::Result<Json<Vec<PublicUser>>> {
+    Ok(Json(UserService::new(context).find_many(ids).await?))
+}
+
 #[get("/user/my_user")]
 pub async fn my_user(context: GeneralContext) -> error::Result<HttpResponse> {
     let user = UserService::new(context).my_user().await?;
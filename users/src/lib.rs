@@ -40,6 +40,7 @@ pub fn create_app(
                 .service(change_user)
                 .service(delete_user)
                 .service(find_user)
+                .service(get_user_data)
                 .service(login)
                 .service(my_user)
                 .service(verify_link)
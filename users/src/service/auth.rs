@@ -308,7 +308,7 @@ impl AuthService {
         };
 
         let linked_account = LinkedAccount {
-            id: user_data.id.clone(),
+            id: user_data.id,
             name: "GitHub".to_string(),
             email: email.clone(),
             url: user_data.html_url,
@@ -327,7 +327,7 @@ impl AuthService {
             is_passwordless: Some(true),
         };
 
-        return Ok((user, linked_account));
+        Ok((user, linked_account))
     }
 
     pub async fn github_auth(&self, data: GithubAuth) -> error::Result<Token> {
@@ -344,7 +344,7 @@ impl AuthService {
             .await?;
 
         if let Some(mut user) = user_service
-            .find_linked_account(linked_account.id.clone(), &linked_account.name)
+            .find_linked_account(linked_account.id, &linked_account.name)
             .await?
         {
             user.current_role = data.clone().current_role;
@@ -542,9 +542,9 @@ impl AuthService {
         };
         let token = token.to_str()?.strip_prefix("Bearer ").unwrap();
         let auth = Auth::from_token(token)?;
-        if auth.is_some() && auth != Some(Auth::None) {
+        if let Some(auth) = auth.filter(|auth| auth != &Auth::None) {
             return Ok(TokenResponce {
-                token: auth.unwrap().to_token()?,
+                token: auth.to_token()?,
             });
         }
         Err(anyhow::anyhow!("Invalid token").code(401))
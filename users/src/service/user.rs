@@ -1,6 +1,6 @@
 use chrono::Utc;
 use common::{
-    access_rules::{AccessRules, Edit, Read},
+    access_rules::{AccessRules, Edit, GetData, Read},
     api::badge::merge,
     auth::Auth,
     context::GeneralContext,
@@ -66,6 +66,20 @@ impl UserService {
         Ok(Some(user.into()))
     }
 
+    pub async fn find_many(&self, ids: Vec<ObjectId>) -> error::Result<Vec<PublicUser>> {
+        let auth = self.context.auth();
+
+        if !GetData.get_access(&auth, ()) {
+            return Err(anyhow::anyhow!("No access to get user data: {:?}", auth).code(400));
+        }
+
+        let users = self.context.try_get_repository::<User<ObjectId>>()?;
+
+        let users = users.find_all_by_ids("id", ids).await?;
+
+        Ok(users.into_iter().map(PublicUser::from).collect())
+    }
+
     pub async fn find_by_email(&self, email: String) -> error::Result<Option<User<ObjectId>>> {
         let auth = self.context.auth();
 
@@ -89,13 +103,13 @@ impl UserService {
             .find_many("linked_accounts.id", &Bson::Int32(id))
             .await?;
 
-        let user = users.iter().cloned().find(|user| {
+        let user = users.iter().find(|&user| {
             if let Some(accounts) = &user.linked_accounts {
                 accounts.iter().any(|account| {
                     account.id == id && account.name == name
                 })
             } else { false }
-        });
+        }).cloned();
 
         Ok(user)
     }